@@ -5,7 +5,7 @@ use std::fmt::{Debug, Display, Formatter};
 use std::num::NonZeroUsize;
 use std::ops::ControlFlow;
 use std::ops::ControlFlow::Continue;
-use std::str::Chars;
+use std::str::{Chars, FromStr};
 use yaml_rust::scanner::*;
 use ParserErr::EOF;
 use TokenType::*;
@@ -15,6 +15,7 @@ pub(crate) type ParserResult<T = ()> = Result<T, ParserErr>;
 pub(crate) enum ParserErr {
     Scan(ScanError),
     EOF,
+    InvalidValue(String),
 }
 
 impl Debug for ParserErr {
@@ -22,6 +23,7 @@ impl Debug for ParserErr {
         match self {
             ParserErr::Scan(e) => Debug::fmt(e, f),
             EOF => f.write_str("EOF"),
+            ParserErr::InvalidValue(msg) => write!(f, "InvalidValue({:?})", msg),
         }
     }
 }
@@ -31,6 +33,7 @@ impl Display for ParserErr {
         match self {
             ParserErr::Scan(e) => Display::fmt(e, f),
             EOF => f.write_str("EOF"),
+            ParserErr::InvalidValue(msg) => f.write_str(msg),
         }
     }
 }
@@ -54,6 +57,23 @@ pub(crate) struct Context<'a> {
     result: Vec<&'a str>,
 }
 
+/// The state of values being skipped by [`Context::skip_next_value`].
+#[derive(Copy, Clone)]
+enum SkipFrame {
+    /// skipping key of a mapping. the value of mapping will be skipped next.
+    MappingKey,
+    /// skipped a value of block mapping.
+    BlockMapping,
+    /// skipped a value of flow mapping.
+    FlowMapping,
+    /// skipped an entry of block sequence without indentation.
+    BlockSequence,
+    /// skipped an entry of block sequence with indentation, which ends with BlockEnd.
+    IndentedBlockSequence,
+    /// skipped an entry of flow sequence.
+    FlowSequence,
+}
+
 macro_rules! return_ok_if_break {
     ($controlflow: expr) => {
         match $controlflow {
@@ -63,7 +83,34 @@ macro_rules! return_ok_if_break {
     };
 }
 
+fn parse_integer<T: FromStr>(name: &str, value: String) -> ParserResult<T> {
+    value
+        .parse()
+        .map_err(|_| ParserErr::InvalidValue(format!("invalid integer for {}: {}", name, value)))
+}
+
 impl<'a> Context<'a> {
+    /// reads the start of the root mapping of an object.
+    /// returns true if the root mapping is a flow mapping.
+    pub(crate) fn root_mapping_start(&mut self) -> ParserResult<bool> {
+        match self.next()? {
+            BlockMappingStart => Ok(false),
+            FlowMappingStart => Ok(true),
+            e => unexpected_token!(e, "BlockMappingStart or FlowMappingStart"),
+        }
+    }
+
+    /// reads the end of the root mapping and the stream.
+    pub(crate) fn root_mapping_end(&mut self, flow: bool) -> ParserResult<()> {
+        if flow {
+            expect_token!(self.next()?, FlowMappingEnd);
+        } else {
+            expect_token!(self.next()?, BlockEnd);
+        }
+        expect_token!(self.next()?, StreamEnd);
+        Ok(())
+    }
+
     pub(crate) fn mapping<'b, R: Default>(
         &'b mut self,
         mut block: impl FnMut(&mut Context<'a>) -> ParserResult<ControlFlow<R>>,
@@ -133,39 +180,148 @@ impl<'a> Context<'a> {
                     unreachable!()
                 }
             }
-            e => panic!("scalar expected but was: {:?}", e),
+            e => unexpected_token!(e, "Scalar"),
+        }
+    }
+
+    /// reads a scalar and checks it is the expected key.
+    pub(crate) fn expect_key(&mut self, key: &str) -> ParserResult {
+        let name = self.next_scalar()?.0;
+        if name != key {
+            return Err(ParserErr::InvalidValue(format!(
+                "expected key {} but was {}",
+                key, name
+            )));
         }
+        Ok(())
     }
 
     pub(crate) fn skip_next_value(&mut self) -> ParserResult {
-        loop {
-            return match self.peek()? {
-                BlockEnd | FlowMappingEnd | Key | Value => return Ok(()),
-                BlockMappingStart | FlowMappingStart => self.mapping(|ctx| {
-                    ctx.skip_next_value()?;
-                    expect_token!(ctx.next()?, Value);
-                    ctx.skip_next_value()?;
-                    Ok(Continue(()))
-                }),
-
-                BlockEntry => Ok(while let BlockEntry = self.peek()? {
+        // this uses explicit stack instead of recursion
+        // to not overflow the stack with deeply nested values.
+        let mut stack = Vec::<SkipFrame>::new();
+        'value: loop {
+            // skip single value
+            match self.peek()? {
+                BlockEnd | FlowMappingEnd | Key | Value => {}
+                BlockMappingStart | FlowMappingStart => {
+                    let frame = match self.next()? {
+                        BlockMappingStart => SkipFrame::BlockMapping,
+                        _ => SkipFrame::FlowMapping,
+                    };
+                    match (self.next()?, frame) {
+                        (Key, _) => {
+                            stack.push(frame);
+                            stack.push(SkipFrame::MappingKey);
+                            continue 'value;
+                        }
+                        (BlockEnd, SkipFrame::BlockMapping) => {}
+                        (FlowMappingEnd, SkipFrame::FlowMapping) => {}
+                        (e, _) => unexpected_token!(e),
+                    }
+                }
+
+                BlockEntry => {
                     self.next()?;
-                    self.skip_next_value()?;
-                }),
+                    stack.push(SkipFrame::BlockSequence);
+                    continue 'value;
+                }
+
+                BlockSequenceStart => {
+                    self.next()?;
+                    match self.next()? {
+                        BlockEntry => {
+                            stack.push(SkipFrame::IndentedBlockSequence);
+                            continue 'value;
+                        }
+                        BlockEnd => {}
+                        e => unexpected_token!(e),
+                    }
+                }
 
                 FlowSequenceStart => {
                     self.next()?;
-                    expect_token!(self.next()?, FlowSequenceEnd);
-                    Ok(())
+                    if let FlowSequenceEnd = self.peek()? {
+                        self.next()?;
+                    } else {
+                        stack.push(SkipFrame::FlowSequence);
+                        continue 'value;
+                    }
                 }
 
                 Scalar(_, _) => {
                     self.next()?;
-                    Ok(())
                 }
 
                 e => unexpected_token!(e),
-            };
+            }
+
+            // the value is skipped. continue on the parent.
+            loop {
+                match stack.last().copied() {
+                    None => return Ok(()),
+                    Some(SkipFrame::MappingKey) => {
+                        stack.pop();
+                        expect_token!(self.next()?, Value);
+                        continue 'value;
+                    }
+                    Some(SkipFrame::BlockMapping) => match self.next()? {
+                        Key => {
+                            stack.push(SkipFrame::MappingKey);
+                            continue 'value;
+                        }
+                        BlockEnd => {
+                            stack.pop();
+                        }
+                        e => unexpected_token!(e),
+                    },
+                    Some(SkipFrame::FlowMapping) => {
+                        let next = match self.next()? {
+                            FlowEntry => self.next()?,
+                            FlowMappingEnd => FlowMappingEnd,
+                            e => unexpected_token!(e),
+                        };
+                        match next {
+                            Key => {
+                                stack.push(SkipFrame::MappingKey);
+                                continue 'value;
+                            }
+                            FlowMappingEnd => {
+                                stack.pop();
+                            }
+                            e => unexpected_token!(e),
+                        }
+                    }
+                    Some(SkipFrame::BlockSequence) => {
+                        if let BlockEntry = self.peek()? {
+                            self.next()?;
+                            continue 'value;
+                        }
+                        stack.pop();
+                    }
+                    Some(SkipFrame::IndentedBlockSequence) => match self.next()? {
+                        BlockEntry => continue 'value,
+                        BlockEnd => {
+                            stack.pop();
+                        }
+                        e => unexpected_token!(e),
+                    },
+                    Some(SkipFrame::FlowSequence) => match self.next()? {
+                        FlowEntry => {
+                            if let FlowSequenceEnd = self.peek()? {
+                                self.next()?;
+                                stack.pop();
+                            } else {
+                                continue 'value;
+                            }
+                        }
+                        FlowSequenceEnd => {
+                            stack.pop();
+                        }
+                        e => unexpected_token!(e),
+                    },
+                }
+            }
         }
     }
 
@@ -178,27 +334,70 @@ impl<'a> Context<'a> {
             let name = ctx.next_scalar()?.0;
             expect_token!(ctx.next()?, Value);
             match name.as_str() {
-                "fileID" => file_id = Some(ctx.next_scalar()?.0.parse().unwrap()),
+                "fileID" => file_id = Some(parse_integer(&name, ctx.next_scalar()?.0)?),
                 "guid" => guid = Some(ctx.next_scalar()?.0),
-                "type" => object_type = Some(ctx.next_scalar()?.0.parse().unwrap()),
-                unknown => panic!("unknown key for object reference: {}", unknown),
+                "type" => object_type = Some(parse_integer(&name, ctx.next_scalar()?.0)?),
+                unknown => {
+                    return Err(ParserErr::InvalidValue(format!(
+                        "unknown key for object reference: {}",
+                        unknown
+                    )))
+                }
             }
             Ok(Continue(()))
         })?;
 
-        let file_id = file_id.expect("fileID does not exist");
+        let file_id = file_id.ok_or_else(|| {
+            ParserErr::InvalidValue("fileID does not exist in object reference".to_owned())
+        })?;
         if file_id == 0 {
             Ok(ObjectReference::null())
         } else if let Some(guid) = guid {
             Ok(ObjectReference::new(
                 file_id,
                 guid,
-                object_type.expect("type does not exist"),
+                object_type.ok_or_else(|| {
+                    ParserErr::InvalidValue("type does not exist in object reference".to_owned())
+                })?,
             ))
         } else {
             Ok(ObjectReference::local(file_id))
         }
     }
+
+    /// parses color like `{r: 0, g: 0, b: 0, a: 1}` and returns `[r, g, b, a]`
+    pub(crate) fn parse_color(&mut self) -> ParserResult<[f32; 4]> {
+        self.parse_floats(["r", "g", "b", "a"])
+    }
+
+    /// parses mapping of floats like `{x: 0, y: 0, z: 0}` and returns values in order of keys
+    pub(crate) fn parse_floats<const N: usize>(
+        &mut self,
+        keys: [&str; N],
+    ) -> ParserResult<[f32; N]> {
+        let mut values: [Option<f32>; N] = [None; N];
+
+        self.mapping(|ctx| {
+            let name = ctx.next_scalar()?.0;
+            expect_token!(ctx.next()?, Value);
+            let index = keys.iter().position(|&key| key == name).ok_or_else(|| {
+                ParserErr::InvalidValue(format!("unknown key for {:?}: {}", keys, name))
+            })?;
+            let value = ctx.next_scalar()?.0;
+            values[index] = Some(value.parse().map_err(|_| {
+                ParserErr::InvalidValue(format!("invalid float for {}: {}", name, value))
+            })?);
+            Ok(Continue(()))
+        })?;
+
+        if values.iter().any(Option::is_none) {
+            return Err(ParserErr::InvalidValue(format!(
+                "some component of {:?} does not exist",
+                keys
+            )));
+        }
+        Ok(values.map(Option::unwrap))
+    }
 }
 
 impl<'a> Context<'a> {
@@ -262,10 +461,24 @@ impl<'a> Context<'a> {
 
     pub(crate) fn skip_until_current_token(&mut self) -> ParserResult {
         log::trace!("skip_until_current_token");
-        self.printed = self.mark_pos(self.mark.unwrap());
+        // the value may be zero-width so never go back to not print twice
+        self.printed = self.printed.max(self.mark_pos(self.mark.unwrap()));
         Ok(())
     }
 
+    /// skips spaces and tabs after the current position in the line.
+    pub(crate) fn skip_trailing_spaces(&mut self) -> ParserResult {
+        log::trace!("skip_trailing_spaces");
+        let rest = &self.yaml[self.printed..];
+        self.printed += rest.len() - rest.trim_start_matches(|c| c == ' ' || c == '\t').len();
+        Ok(())
+    }
+
+    /// returns the part of yaml not written nor skipped until current token.
+    pub(crate) fn unprinted_until_current_token(&self) -> &'a str {
+        &self.yaml[self.printed..self.mark_pos(self.mark.unwrap())]
+    }
+
     fn mark_pos(&self, mark: Marker) -> usize {
         self.yaml[..mark.end().index()].trim_end().len()
     }
@@ -285,10 +498,10 @@ impl<'a> Context<'a> {
     }
 
     fn append(&mut self, index: usize) {
-        if index == self.printed {
+        // on malformed yaml the current token may end before the printed position.
+        if index <= self.printed {
             return;
         }
-        assert!(self.printed < index);
         let index = unsafe { NonZeroUsize::new_unchecked(index) };
         if let Some((first, end)) = self.will_write.as_mut() {
             if end.get() == self.printed {
@@ -1,20 +1,67 @@
 use super::super::ObjectReference;
-use super::context::{Context, ParserResult};
+use super::context::{Context, ParserErr, ParserResult};
 use crate::clean::YamlSection;
 use lazy_static::lazy_static;
+use log::{debug, warn};
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::ops::ControlFlow::{Break, Continue};
 use yaml_rust::scanner::*;
 use TokenType::*;
 
-pub(in super::super) fn filter(sections: &mut [YamlSection]) -> ParserResult {
+/// The configuration of rules applied by this filter.
+#[derive(Default)]
+pub(in super::super) struct FilterConfig {
+    /// If specified, m_IndirectSpecularColor of RenderSettings will be replaced only if
+    /// each component is within this tolerance from the default value.
+    /// If not specified, m_IndirectSpecularColor will always be replaced.
+    pub(in super::super) indirect_specular_color_tolerance: Option<f32>,
+    /// If true, empty m_EditorClassIdentifier of MonoBehaviour will be normalized to
+    /// the form without trailing whitespace.
+    pub(in super::super) normalize_editor_class_identifier: bool,
+    /// If true, ViewPosition of VRCAvatarDescriptor will be normalized if it's nearly
+    /// the default value.
+    pub(in super::super) normalize_view_position: bool,
+    /// If true, `-0` in m_LocalPosition and m_LocalScale of Transform will be replaced with `0`.
+    pub(in super::super) normalize_negative_zero: bool,
+    /// If true, trailing spaces of quoted m_Name of GameObject and MonoBehaviour will be trimmed.
+    pub(in super::super) trim_name: bool,
+    /// If true, only whole computed objects like PipelineSaver are removed and
+    /// values of fields are kept as is.
+    pub(in super::super) objects_only: bool,
+    /// If true, `.Array.size` modifications of PrefabInstance will be removed if there are
+    /// no `.Array.data[]` modifications of the same array.
+    pub(in super::super) remove_orphan_array_size: bool,
+    /// If true, null animatorController in baseAnimationLayers of VRCAvatarDescriptor
+    /// will be normalized to `{fileID: 0}`.
+    pub(in super::super) normalize_default_animator_controller: bool,
+    /// If true, shapes of automatic collider_* of VRCAvatarDescriptor will be normalized.
+    pub(in super::super) normalize_auto_colliders: bool,
+    /// If true, m_AmbientProbe of RenderSettings will be normalized to zero unless
+    /// m_AmbientMode is Custom, which means the probe is specified by user.
+    pub(in super::super) normalize_ambient_probe: bool,
+    /// If true, m_RemovedComponents of PrefabInstance will be sorted by fileID.
+    pub(in super::super) sort_removed_components: bool,
+    /// If true, null references of VRCStation and VRCAvatarPedestal written with guid and type
+    /// will be normalized to `{fileID: 0}`.
+    pub(in super::super) normalize_station_null_references: bool,
+}
+
+pub(in super::super) fn filter(
+    sections: &mut [YamlSection],
+    config: &FilterConfig,
+) -> ParserResult {
     for section in sections {
+        // a heading without body like trailing `---` has nothing to filter
+        if section.filtered.is_empty() {
+            continue;
+        }
         match &section.filtered {
             Cow::Borrowed(b) => {
-                section.filtered = filter_yaml(&b)?;
+                section.filtered = filter_yaml_with_config(&b, config)?;
             }
             Cow::Owned(o) => {
-                section.filtered = match filter_yaml(&o)? {
+                section.filtered = match filter_yaml_with_config(&o, config)? {
                     Cow::Borrowed(b) => b.to_owned().into(),
                     Cow::Owned(o) => o.into(),
                 }
@@ -24,19 +71,39 @@ pub(in super::super) fn filter(sections: &mut [YamlSection]) -> ParserResult {
     Ok(())
 }
 
+#[cfg(test)]
 fn filter_yaml(yaml: &str) -> ParserResult<Cow<str>> {
+    filter_yaml_with_config(yaml, &FilterConfig::default())
+}
+
+fn filter_yaml_with_config<'a>(yaml: &'a str, config: &FilterConfig) -> ParserResult<Cow<'a, str>> {
     assert!(!yaml.is_empty());
     let mut ctx = Context::new(&yaml);
 
     expect_token!(ctx.next()?, StreamStart(_));
-    expect_token!(ctx.next()?, BlockMappingStart);
+    let flow_root = ctx.root_mapping_start()?;
     expect_token!(ctx.next()?, Key);
     let object_type = ctx.next_scalar()?.0;
     expect_token!(ctx.next()?, Value);
+    if !matches!(ctx.peek()?, BlockMappingStart | FlowMappingStart) {
+        // objects without fields like minimal stripped objects have nothing to filter
+        return Ok(yaml.into());
+    }
     let omit_current_value = match object_type.as_str() {
-        "MonoBehaviour" => mono_behaviour(&mut ctx)?,
-        "PrefabInstance" => prefab_instance(&mut ctx)?,
-        "RenderSettings" => render_settings(&mut ctx)?,
+        "MonoBehaviour" if config.objects_only => mono_behaviour_objects_only(&mut ctx)?,
+        _ if config.objects_only => return Ok(yaml.into()),
+        "MonoBehaviour" => mono_behaviour(&mut ctx, yaml, config)?,
+        "PrefabInstance" => {
+            let modified_arrays = if config.remove_orphan_array_size {
+                Some(modified_arrays(yaml)?)
+            } else {
+                None
+            };
+            prefab_instance(&mut ctx, config, modified_arrays.as_ref())?
+        }
+        "RenderSettings" => render_settings(&mut ctx, config)?,
+        "Transform" if config.normalize_negative_zero => transform(&mut ctx)?,
+        "GameObject" if config.trim_name => game_object(&mut ctx)?,
         _ => {
             // nothing to do fot this object. print all and return
             return Ok(yaml.into());
@@ -48,8 +115,7 @@ fn filter_yaml(yaml: &str) -> ParserResult<Cow<str>> {
     }
 
     // closings
-    assert!(matches!(ctx.next()?, BlockEnd), "MappingEnd expected");
-    assert!(matches!(ctx.next()?, StreamEnd), "StreamEnd expected");
+    ctx.root_mapping_end(flow_root)?;
 
     Ok(ctx.finish().into())
 }
@@ -57,17 +123,32 @@ fn filter_yaml(yaml: &str) -> ParserResult<Cow<str>> {
 lazy_static! {
     static ref PIPELINE_SAVER_REFERENCE: ObjectReference =
         ObjectReference::new(229740497, "4ecd63eff847044b68db9453ce219299".to_owned(), 3);
+    static ref AVATAR_DESCRIPTOR_REFERENCE: ObjectReference =
+        ObjectReference::new(542108242, "67cc4cb7839cd3741b63733d5adf0442".to_owned(), 3);
+    static ref SCENE_DESCRIPTOR_REFERENCE: ObjectReference =
+        ObjectReference::new(-17141911, "661092b4961be7145bfbe56e1e62337b".to_owned(), 3);
+    static ref STATION_REFERENCE: ObjectReference =
+        ObjectReference::new(1073094524, "661092b4961be7145bfbe56e1e62337b".to_owned(), 3);
+    static ref AVATAR_PEDESTAL_REFERENCE: ObjectReference =
+        ObjectReference::new(1619588052, "661092b4961be7145bfbe56e1e62337b".to_owned(), 3);
 }
 
+/// The default ViewPosition of VRCAvatarDescriptor
+const DEFAULT_VIEW_POSITION: [f32; 3] = [0.0, 1.6, 0.2];
+const VIEW_POSITION_TOLERANCE: f32 = 1e-4;
+
 /// MonoBehaviour
-fn mono_behaviour(ctx: &mut Context) -> ParserResult<bool> {
+fn mono_behaviour(ctx: &mut Context, yaml: &str, config: &FilterConfig) -> ParserResult<bool> {
+    let mut script: Option<ObjectReference> = None;
     ctx.mapping(|ctx| {
         let name = ctx.next_scalar()?.0;
         expect_token!(ctx.next()?, Value);
         match name.as_str() {
-            "serializedVersion" => {
-                assert_eq!(ctx.next_scalar()?.0, "2", "unknown serializedVersion")
-            }
+            "serializedVersion" => match ctx.next_scalar()?.0.as_str() {
+                "2" => {}
+                // rules for fields doesn't depend on serializedVersion so continue
+                v => warn!("unknown serializedVersion of MonoBehaviour: {}", v),
+            },
             "m_Script" => {
                 let object_reference = ctx.parse_object_reference()?;
                 if object_reference == *PIPELINE_SAVER_REFERENCE {
@@ -76,6 +157,17 @@ fn mono_behaviour(ctx: &mut Context) -> ParserResult<bool> {
                     // https://github.com/anatawa12/git-vrc/issues/3
                     return Ok(Break(true));
                 }
+                script = Some(object_reference);
+            }
+            "m_Name" if config.trim_name => trim_quoted_trailing_spaces(ctx)?,
+            "m_EditorClassIdentifier" if config.normalize_editor_class_identifier => {
+                // empty m_EditorClassIdentifier may be toggled between with and without
+                // trailing whitespace so use the form without whitespace.
+                ctx.write_until_current_token()?;
+                if ctx.next_scalar()?.0.is_empty() {
+                    ctx.skip_until_current_token()?;
+                    ctx.skip_trailing_spaces()?;
+                }
             }
             "serializedUdonProgramAsset" | "serializedProgramAsset" => {
                 // for serializedUdonProgramAsset or serializedProgramAsset with mapping,
@@ -89,16 +181,14 @@ fn mono_behaviour(ctx: &mut Context) -> ParserResult<bool> {
                 // fallbackStatus of PipelineManager is automatically computed.
                 ctx.write_until_current_token()?;
                 ctx.skip_next_value()?;
+                log_replaced_value("fallbackStatus", ctx.unprinted_until_current_token(), "0");
                 ctx.append_str(" 0");
                 ctx.skip_until_current_token()?;
             }
             "animationHashSet" => {
                 // animationHashSet of VRCAvatarDescriptor is automatically computed.
                 // https://github.com/anatawa12/git-vrc/issues/13
-                ctx.write_until_current_token()?;
-                ctx.skip_next_value()?;
-                ctx.append_str(" []");
-                ctx.skip_until_current_token()?;
+                clear_next_sequence(ctx)?;
             }
             "layerCollisionArr" => {
                 // layerCollisionArr of VRC_SceneDescriptor is automatically computed.
@@ -143,6 +233,19 @@ fn mono_behaviour(ctx: &mut Context) -> ParserResult<bool> {
                 ));
                 ctx.skip_until_current_token()?;
             }
+            "capacity" if script.as_ref() == Some(&*SCENE_DESCRIPTOR_REFERENCE) => {
+                // capacity of VRC_SceneDescriptor is serialized with layerCollisionArr so
+                // normalize with layerCollisionArr, which is always normalized to the default,
+                // to not drift. layerCollisionArr may be after capacity so find it from whole yaml
+                if has_layer_collision_arr(yaml)? {
+                    ctx.write_until_current_token()?;
+                    ctx.skip_next_value()?;
+                    ctx.append_str(" 0");
+                    ctx.skip_until_current_token()?;
+                } else {
+                    ctx.skip_next_value()?;
+                }
+            }
             "completedSDKPipeline" => {
                 // completedSDKPipeline of PipelineManager is automatically computed.
                 // https://github.com/anatawa12/git-vrc/issues/17
@@ -151,8 +254,46 @@ fn mono_behaviour(ctx: &mut Context) -> ParserResult<bool> {
                 ctx.append_str(" 0");
                 ctx.skip_until_current_token()?;
             }
+            "ViewPosition"
+                if config.normalize_view_position
+                    && script.as_ref() == Some(&*AVATAR_DESCRIPTOR_REFERENCE) =>
+            {
+                // ViewPosition of VRCAvatarDescriptor may be recomputed with floating point
+                // noise. normalize only nearly default value to keep user-set value.
+                ctx.write_until_current_token()?;
+                let position = ctx.parse_floats(["x", "y", "z"])?;
+                if position
+                    .into_iter()
+                    .zip(DEFAULT_VIEW_POSITION)
+                    .all(|(value, default)| (value - default).abs() <= VIEW_POSITION_TOLERANCE)
+                {
+                    ctx.append_str(" {x: 0, y: 1.6, z: 0.2}");
+                    ctx.skip_until_current_token()?;
+                }
+            }
+            // collider_* of VRCAvatarDescriptor
+            collider
+                if config.normalize_auto_colliders
+                    && collider.starts_with("collider_")
+                    && script.as_ref() == Some(&*AVATAR_DESCRIPTOR_REFERENCE) =>
+            {
+                avatar_collider(ctx)?
+            }
+            // references of VRCStation and VRCAvatarPedestal
+            "stationEnterPlayerLocation" | "stationExitPlayerLocation" | "animatorController"
+                if config.normalize_station_null_references
+                    && script.as_ref() == Some(&*STATION_REFERENCE) =>
+            {
+                normalize_null_reference(ctx)?
+            }
+            "Placement"
+                if config.normalize_station_null_references
+                    && script.as_ref() == Some(&*AVATAR_PEDESTAL_REFERENCE) =>
+            {
+                normalize_null_reference(ctx)?
+            }
             // baseAnimationLayers of VRCAvatarDescriptor
-            "baseAnimationLayers" => mono_behaviour_base_animation_layers(ctx)?,
+            "baseAnimationLayers" => mono_behaviour_base_animation_layers(ctx, config)?,
             // foldout_* of VRCPhysBone
             // https://github.com/anatawa12/git-vrc/issues/20
             "foldout_transforms"
@@ -178,18 +319,179 @@ fn mono_behaviour(ctx: &mut Context) -> ParserResult<bool> {
                 // (VRC_WorldDescriptor) is runtime (build-time) generated field so
                 // it should not be tracked via git
                 // https://github.com/anatawa12/git-vrc/issues/5
-                ctx.write_until_current_token()?;
-                ctx.append_str(" []");
-                ctx.skip_next_value()?;
+                clear_next_sequence(ctx)?;
+            }
+            _ => ctx.skip_next_value()?,
+        }
+        Ok(Continue(()))
+    })
+}
+
+/// GameObject
+fn game_object(ctx: &mut Context) -> ParserResult<bool> {
+    ctx.mapping(|ctx| {
+        let name = ctx.next_scalar()?.0;
+        expect_token!(ctx.next()?, Value);
+        match name.as_str() {
+            "m_Name" => trim_quoted_trailing_spaces(ctx)?,
+            _ => ctx.skip_next_value()?,
+        }
+        Ok(Continue(()))
+    })
+}
+
+/// trims trailing spaces in the next quoted scalar like `'Name  '`.
+/// plain scalars cannot have trailing spaces so those are kept as is.
+fn trim_quoted_trailing_spaces(ctx: &mut Context) -> ParserResult {
+    let quote = match ctx.peek()? {
+        Scalar(TScalarStyle::SingleQuoted, _) => "'",
+        Scalar(TScalarStyle::DoubleQuoted, _) => "\"",
+        _ => return ctx.skip_next_value(),
+    };
+    ctx.write_until_current_token()?;
+    ctx.skip_next_value()?;
+    let quoted = ctx.unprinted_until_current_token();
+    let inner = &quoted[..quoted.len() - quote.len()];
+    let trimmed = inner.trim_end_matches(' ');
+    // keep escaped space of double quoted scalar like "Name\ "
+    if trimmed.len() != inner.len() && !(quote == "\"" && trimmed.ends_with('\\')) {
+        ctx.append_str(trimmed);
+        ctx.append_str(quote);
+        ctx.skip_until_current_token()?;
+    }
+    Ok(())
+}
+
+/// Transform
+fn transform(ctx: &mut Context) -> ParserResult<bool> {
+    ctx.mapping(|ctx| {
+        let name = ctx.next_scalar()?.0;
+        expect_token!(ctx.next()?, Value);
+        match name.as_str() {
+            // -0 and 0 are toggled without any change in the editor
+            "m_LocalPosition" | "m_LocalScale" => normalize_negative_zeros(ctx)?,
+            _ => ctx.skip_next_value()?,
+        }
+        Ok(Continue(()))
+    })
+}
+
+/// replaces `-0` values in the next mapping like `{x: -0, y: 1, z: 0}` with `0`.
+fn normalize_negative_zeros(ctx: &mut Context) -> ParserResult {
+    ctx.mapping(|ctx| {
+        ctx.next_scalar()?;
+        expect_token!(ctx.next()?, Value);
+        match ctx.next_scalar()? {
+            (value, TScalarStyle::Plain) if value == "-0" => {
+                ctx.write_until_last_token()?;
+                ctx.append_str(" 0");
                 ctx.skip_until_current_token()?;
             }
+            _ => {}
+        }
+        Ok(Continue(()))
+    })
+}
+
+/// MonoBehaviour without field rules. only removes PipelineSaver
+fn mono_behaviour_objects_only(ctx: &mut Context) -> ParserResult<bool> {
+    ctx.mapping(|ctx| {
+        let name = ctx.next_scalar()?.0;
+        expect_token!(ctx.next()?, Value);
+        match name.as_str() {
+            "m_Script" => {
+                if ctx.parse_object_reference()? == *PIPELINE_SAVER_REFERENCE {
+                    // https://github.com/anatawa12/git-vrc/issues/3
+                    return Ok(Break(true));
+                }
+            }
             _ => ctx.skip_next_value()?,
         }
         Ok(Continue(()))
     })
 }
 
-fn mono_behaviour_base_animation_layers(ctx: &mut Context) -> ParserResult {
+/// collider_* of VRCAvatarDescriptor.
+/// shape of automatic collider is recomputed from the avatar so normalize to the default.
+fn avatar_collider(ctx: &mut Context) -> ParserResult {
+    let mut automatic = false;
+    ctx.mapping(|ctx| {
+        let key = ctx.next_scalar()?.0;
+        expect_token!(ctx.next()?, Value);
+        let default = match key.as_str() {
+            "state" => {
+                // 0: Automatic, 1: Custom, 2: Disabled
+                automatic = ctx.next_scalar()?.0 == "0";
+                return Ok(Continue(()));
+            }
+            "radius" | "height" => " 0",
+            "position" => " {x: 0, y: 0, z: 0}",
+            "rotation" => " {x: 0, y: 0, z: 0, w: 1}",
+            _ => {
+                ctx.skip_next_value()?;
+                return Ok(Continue(()));
+            }
+        };
+        if automatic {
+            ctx.write_until_current_token()?;
+            ctx.skip_next_value()?;
+            ctx.append_str(default);
+            ctx.skip_until_current_token()?;
+        } else {
+            ctx.skip_next_value()?;
+        }
+        Ok(Continue(()))
+    })
+}
+
+/// replaces the next value with empty sequence.
+/// if the value is already an empty sequence, this keeps it as is.
+fn clear_next_sequence(ctx: &mut Context) -> ParserResult {
+    ctx.write_until_current_token()?;
+    ctx.skip_next_value()?;
+    if ctx.unprinted_until_current_token().trim() != "[]" {
+        ctx.append_str(" []");
+        ctx.skip_until_current_token()?;
+    }
+    Ok(())
+}
+
+/// replaces the next value with `[]` if it's an empty sequence in other form
+/// like `[ ]` or null. non-empty sequences are kept as is.
+fn normalize_empty_sequence(ctx: &mut Context) -> ParserResult {
+    match ctx.peek()? {
+        FlowSequenceStart => {}
+        Scalar(TScalarStyle::Plain, value) if value == "~" || value == "null" => {}
+        _ => return ctx.skip_next_value(),
+    }
+    ctx.write_until_current_token()?;
+    ctx.skip_next_value()?;
+    let value = ctx.unprinted_until_current_token().trim();
+    let is_empty = match value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        Some(inner) => inner.trim().is_empty(),
+        None => true,
+    };
+    if is_empty && value != "[]" {
+        ctx.append_str(" []");
+        ctx.skip_until_current_token()?;
+    }
+    Ok(())
+}
+
+/// replaces null reference like `{fileID: 0, guid: <guid>, type: 2}` with `{fileID: 0}`
+fn normalize_null_reference(ctx: &mut Context) -> ParserResult {
+    if !matches!(ctx.peek()?, FlowMappingStart) {
+        return ctx.skip_next_value();
+    }
+    ctx.write_until_current_token()?;
+    if ctx.parse_object_reference()?.is_null() {
+        ctx.append_str(" {fileID: 0}");
+        ctx.skip_until_current_token()?;
+    }
+    Ok(())
+}
+
+fn mono_behaviour_base_animation_layers(ctx: &mut Context, config: &FilterConfig) -> ParserResult {
     ctx.write_until_current_token()?;
 
     ctx.sequence(|ctx| {
@@ -206,6 +508,10 @@ fn mono_behaviour_base_animation_layers(ctx: &mut Context) -> ParserResult {
                     ctx.append_str(" {fileID: 0}");
                     ctx.skip_until_current_token()?;
                 }
+                "animatorController" if config.normalize_default_animator_controller => {
+                    // null controller of default layer may be written with guid and type
+                    normalize_null_reference(ctx)?
+                }
                 _ => ctx.skip_next_value()?,
             }
 
@@ -216,35 +522,97 @@ fn mono_behaviour_base_animation_layers(ctx: &mut Context) -> ParserResult {
     Ok(())
 }
 
+/// arrays modified with `.Array.data[]` entries in a PrefabInstance.
+/// pairs of the target and the path of array like `DynamicMaterials.Array`
+type ModifiedArrays = HashSet<(ObjectReference, String)>;
+
 /// PrefabInstance
-fn prefab_instance(ctx: &mut Context) -> ParserResult<bool> {
+fn prefab_instance(
+    ctx: &mut Context,
+    config: &FilterConfig,
+    modified_arrays: Option<&ModifiedArrays>,
+) -> ParserResult<bool> {
     ctx.mapping(|ctx| {
         let key = ctx.next_scalar()?.0;
         expect_token!(ctx.next()?, Value);
         match key.as_str() {
-            "serializedVersion" => {
-                assert_eq!(ctx.next_scalar()?.0, "2", "unknown serializedVersion")
-            }
-            "m_Modification" => prefab_instance_modification(ctx)?,
+            "serializedVersion" => match ctx.next_scalar()?.0.as_str() {
+                // 3 is used since Unity 2022
+                "2" | "3" => {}
+                // rules for modifications doesn't depend on serializedVersion so continue
+                v => warn!("unknown serializedVersion of PrefabInstance: {}", v),
+            },
+            "m_Modification" => prefab_instance_modification(ctx, config, modified_arrays)?,
             _ => ctx.skip_next_value()?,
         }
         Ok(Continue(()))
     })
 }
 
-fn prefab_instance_modification(ctx: &mut Context) -> ParserResult {
+fn prefab_instance_modification(
+    ctx: &mut Context,
+    config: &FilterConfig,
+    modified_arrays: Option<&ModifiedArrays>,
+) -> ParserResult {
     ctx.mapping(|ctx| {
         let key = ctx.next_scalar()?.0;
         expect_token!(ctx.next()?, Value);
         match key.as_str() {
-            "m_Modifications" => prefab_instance_modifications_sequence(ctx)?,
+            "m_Modifications" => {
+                prefab_instance_modifications_sequence(ctx, config, modified_arrays)?
+            }
+            "m_RemovedComponents" if config.sort_removed_components => {
+                sort_removed_components(ctx)?
+            }
+            "m_RemovedComponents" => normalize_empty_sequence(ctx)?,
             _ => ctx.skip_next_value()?,
         }
         Ok(Continue(()))
     })
 }
 
-fn prefab_instance_modifications_sequence(ctx: &mut Context) -> ParserResult {
+/// m_RemovedComponents may be reordered by unity so sort them by fileID
+fn sort_removed_components(ctx: &mut Context) -> ParserResult {
+    if !matches!(ctx.peek()?, BlockEntry) {
+        // empty or flow sequence is not generated by unity
+        return normalize_empty_sequence(ctx);
+    }
+    ctx.write_until_current_token()?;
+
+    // each unprinted text ends with the entry. the text for an entry includes
+    // the line break and the indent before `-` so entries can be written in any order
+    let mut entries = Vec::new();
+    ctx.sequence(|ctx| {
+        let reference = ctx.parse_object_reference()?;
+        entries.push((reference.file_id, ctx.unprinted_until_current_token()));
+        Ok(Continue(()))
+    })?;
+    let mut start = 0;
+    let mut entries = entries
+        .into_iter()
+        .map(|(file_id, unprinted)| {
+            let entry = &unprinted[start..];
+            start = unprinted.len();
+            (file_id, entry)
+        })
+        .collect::<Vec<_>>();
+
+    if entries.windows(2).all(|pair| pair[0].0 <= pair[1].0) {
+        return Ok(());
+    }
+    entries.sort_by_key(|&(file_id, _)| file_id);
+    ctx.skip_until_current_token()?;
+    for (_, entry) in entries {
+        ctx.append_str(entry);
+    }
+    Ok(())
+}
+
+fn prefab_instance_modifications_sequence(
+    ctx: &mut Context,
+    config: &FilterConfig,
+    modified_arrays: Option<&ModifiedArrays>,
+) -> ParserResult {
     ctx.write_until_current_token()?;
 
     let mut some_written = false;
@@ -254,6 +622,10 @@ fn prefab_instance_modifications_sequence(ctx: &mut Context) -> ParserResult {
         let mut property_path: Option<String> = None;
         let mut value: Option<String> = None;
         let mut object_reference: Option<ObjectReference> = None;
+        // non-scalar values are not generated by unity so keep those modifications as is
+        let mut non_scalar_value = false;
+        // modifications with unknown keys may have meaning this tool doesn't know so keep them
+        let mut unknown_key_found = false;
 
         ctx.mapping(|ctx| {
             let key = ctx.next_scalar()?.0;
@@ -262,9 +634,26 @@ fn prefab_instance_modifications_sequence(ctx: &mut Context) -> ParserResult {
             match key.as_str() {
                 "target" => target = Some(ctx.parse_object_reference()?),
                 "propertyPath" => property_path = Some(ctx.next_scalar()?.0),
-                "value" => value = Some(ctx.next_scalar()?.0),
+                "value" => match ctx.peek()? {
+                    FlowMappingStart | FlowSequenceStart => {
+                        ctx.skip_next_value()?;
+                        non_scalar_value = true;
+                        value = Some(String::new());
+                    }
+                    _ => {
+                        // `~` is null in yaml so treat as same as empty value
+                        value = Some(match ctx.next_scalar()? {
+                            (v, TScalarStyle::Plain) if v == "~" => String::new(),
+                            (v, _) => v,
+                        })
+                    }
+                },
                 "objectReference" => object_reference = Some(ctx.parse_object_reference()?),
-                unknown => panic!("unknown key on PrefabInstance modifications: {}", unknown),
+                unknown => {
+                    warn!("unknown key on PrefabInstance modifications: {}", unknown);
+                    ctx.skip_next_value()?;
+                    unknown_key_found = true;
+                }
             }
 
             Ok(Continue(()))
@@ -273,16 +662,24 @@ fn prefab_instance_modifications_sequence(ctx: &mut Context) -> ParserResult {
         // check if current modification is for keep or remove
         #[allow(unused_variables)]
         {
-            let target = target.expect("target not specified in prefab modifications");
-            let value = value.expect("value not specified in prefab modifications");
-            let property_path =
-                property_path.expect("propertyPath not specified in prefab modifications");
-            let object_reference =
-                object_reference.expect("objectReference not specified in prefab modifications");
-
-            if should_omit(&property_path, &value, &object_reference) {
+            let target = required(target, "target")?;
+            let value = required(value, "value")?;
+            let property_path = required(property_path, "propertyPath")?;
+            let object_reference = required(object_reference, "objectReference")?;
+
+            if unknown_key_found {
+                some_written = true;
+                ctx.write_until_last_token()?
+            } else if !non_scalar_value
+                && should_omit(&property_path, &value, &object_reference, config)
+            {
                 // https://github.com/anatawa12/git-vrc/issues/5
                 ctx.skip_until_last_token()?
+            } else if modified_arrays
+                .map(|arrays| is_orphan_array_size(target, &property_path, arrays))
+                .unwrap_or(false)
+            {
+                ctx.skip_until_last_token()?
             } else {
                 some_written = true;
                 ctx.write_until_last_token()?
@@ -300,8 +697,113 @@ fn prefab_instance_modifications_sequence(ctx: &mut Context) -> ParserResult {
     Ok(())
 }
 
+fn required<T>(value: Option<T>, key: &str) -> ParserResult<T> {
+    value.ok_or_else(|| {
+        ParserErr::InvalidValue(format!("{} not specified in prefab modifications", key))
+    })
+}
+
+/// collects arrays modified with `.Array.data[]` entries in the PrefabInstance.
+fn modified_arrays(yaml: &str) -> ParserResult<ModifiedArrays> {
+    let mut arrays = ModifiedArrays::new();
+    for_each_modification(yaml, |target, property_path| {
+        if let Some(index) = property_path.find(".Array.data[") {
+            let array = &property_path[..index + ".Array".len()];
+            arrays.insert((target, array.to_owned()));
+        }
+    })?;
+    Ok(arrays)
+}
+
+/// calls `f` with target and propertyPath of each modification in the PrefabInstance.
+fn for_each_modification(yaml: &str, mut f: impl FnMut(ObjectReference, String)) -> ParserResult {
+    let mut ctx = Context::new(yaml);
+
+    expect_token!(ctx.next()?, StreamStart(_));
+    ctx.root_mapping_start()?;
+    expect_token!(ctx.next()?, Key);
+    ctx.next_scalar()?;
+    expect_token!(ctx.next()?, Value);
+    ctx.mapping(|ctx| {
+        let key = ctx.next_scalar()?.0;
+        expect_token!(ctx.next()?, Value);
+        if key != "m_Modification" {
+            ctx.skip_next_value()?;
+            return Ok(Continue(()));
+        }
+        ctx.mapping(|ctx| {
+            let key = ctx.next_scalar()?.0;
+            expect_token!(ctx.next()?, Value);
+            if key != "m_Modifications" {
+                ctx.skip_next_value()?;
+                return Ok(Continue(()));
+            }
+            ctx.sequence(|ctx| {
+                let mut target: Option<ObjectReference> = None;
+                let mut property_path: Option<String> = None;
+                ctx.mapping(|ctx| {
+                    let key = ctx.next_scalar()?.0;
+                    expect_token!(ctx.next()?, Value);
+                    match key.as_str() {
+                        "target" => target = Some(ctx.parse_object_reference()?),
+                        "propertyPath" => property_path = Some(ctx.next_scalar()?.0),
+                        _ => ctx.skip_next_value()?,
+                    }
+                    Ok(Continue(()))
+                })?;
+                if let (Some(target), Some(property_path)) = (target, property_path) {
+                    f(target, property_path);
+                }
+                Ok(Continue(()))
+            })?;
+            Ok(Continue(()))
+        })?;
+        Ok(Continue(()))
+    })
+}
+
+/// returns true if the MonoBehaviour has layerCollisionArr.
+fn has_layer_collision_arr(yaml: &str) -> ParserResult<bool> {
+    let mut ctx = Context::new(yaml);
+
+    expect_token!(ctx.next()?, StreamStart(_));
+    ctx.root_mapping_start()?;
+    expect_token!(ctx.next()?, Key);
+    ctx.next_scalar()?;
+    expect_token!(ctx.next()?, Value);
+    ctx.mapping(|ctx| {
+        let key = ctx.next_scalar()?.0;
+        expect_token!(ctx.next()?, Value);
+        if key == "layerCollisionArr" {
+            return Ok(Break(true));
+        }
+        ctx.skip_next_value()?;
+        Ok(Continue(()))
+    })
+}
+
+/// returns true if the modification is `.Array.size` of the array without `.Array.data[]`
+/// modifications. those are meaningless churn of generic arrays.
+fn is_orphan_array_size(
+    target: ObjectReference,
+    property_path: &str,
+    modified_arrays: &ModifiedArrays,
+) -> bool {
+    match property_path.strip_suffix(".size") {
+        Some(array) if array.ends_with(".Array") => {
+            !modified_arrays.contains(&(target, array.to_owned()))
+        }
+        _ => false,
+    }
+}
+
 #[allow(unused_variables)]
-fn should_omit(property_path: &str, value: &str, object_reference: &ObjectReference) -> bool {
+fn should_omit(
+    property_path: &str,
+    value: &str,
+    object_reference: &ObjectReference,
+    config: &FilterConfig,
+) -> bool {
     if property_path == "serializedProgramAsset" && value == "" {
         return true;
     }
@@ -318,6 +820,13 @@ fn should_omit(property_path: &str, value: &str, object_reference: &ObjectRefere
         // https://github.com/anatawa12/git-vrc/issues/17
         return true;
     }
+    if (property_path == "m_IndirectSpecularColor"
+        || property_path.starts_with("m_IndirectSpecularColor."))
+        && object_reference.is_null()
+    {
+        // m_IndirectSpecularColor of RenderSettings is automatically computed.
+        return is_default_indirect_specular_color(property_path, value, config);
+    }
     if property_path.starts_with("DynamicMaterials.Array")
         || property_path.starts_with("DynamicPrefabs.Array")
         || property_path.starts_with("animationHashSet.Array")
@@ -352,18 +861,64 @@ fn should_omit(property_path: &str, value: &str, object_reference: &ObjectRefere
     return false;
 }
 
+/// returns true if the modification of m_IndirectSpecularColor is within
+/// `indirect_specular_color_tolerance` from the default value.
+fn is_default_indirect_specular_color(
+    property_path: &str,
+    value: &str,
+    config: &FilterConfig,
+) -> bool {
+    let tolerance = match config.indirect_specular_color_tolerance {
+        None => return true,
+        Some(tolerance) => tolerance,
+    };
+    let default = match property_path.strip_prefix("m_IndirectSpecularColor.") {
+        Some("r" | "g" | "b") => 0.0,
+        Some("a") => 1.0,
+        _ => return false,
+    };
+    match value.parse::<f32>() {
+        Ok(value) => (value - default).abs() <= tolerance,
+        Err(_) => false,
+    }
+}
+
 /// RenderSettings
-fn render_settings(ctx: &mut Context) -> ParserResult<bool> {
+fn render_settings(ctx: &mut Context, config: &FilterConfig) -> ParserResult<bool> {
+    // m_AmbientProbe is computed from other settings unless m_AmbientMode is Custom
+    let mut custom_ambient = false;
     ctx.mapping(|ctx| {
         let name = ctx.next_scalar()?.0;
         expect_token!(ctx.next()?, Value);
         match name.as_str() {
+            "m_AmbientMode" => custom_ambient = ctx.next_scalar()?.0 == AMBIENT_MODE_CUSTOM,
+            "m_AmbientProbe" if config.normalize_ambient_probe && !custom_ambient => {
+                render_settings_ambient_probe(ctx)?
+            }
             "m_IndirectSpecularColor" => {
                 // for m_IndirectSpecularColor of m_IndirectSpecularColor,
                 ctx.write_until_current_token()?;
-                ctx.skip_next_value()?;
-                ctx.append_str(" {r: 0, g: 0, b: 0, a: 1}");
-                ctx.skip_until_current_token()?;
+                let replace = match config.indirect_specular_color_tolerance {
+                    None => {
+                        ctx.skip_next_value()?;
+                        true
+                    }
+                    Some(tolerance) => {
+                        let [r, g, b, a] = ctx.parse_color()?;
+                        [r, g, b, a - 1.0]
+                            .into_iter()
+                            .all(|diff| diff.abs() <= tolerance)
+                    }
+                };
+                if replace {
+                    log_replaced_value(
+                        "m_IndirectSpecularColor",
+                        ctx.unprinted_until_current_token(),
+                        "{r: 0, g: 0, b: 0, a: 1}",
+                    );
+                    ctx.append_str(" {r: 0, g: 0, b: 0, a: 1}");
+                    ctx.skip_until_current_token()?;
+                }
             }
             _ => ctx.skip_next_value()?,
         }
@@ -371,6 +926,40 @@ fn render_settings(ctx: &mut Context) -> ParserResult<bool> {
     })
 }
 
+const AMBIENT_MODE_CUSTOM: &str = "4";
+
+/// m_AmbientProbe of RenderSettings. coefficients like `sh[ 0]` are replaced with 0
+fn render_settings_ambient_probe(ctx: &mut Context) -> ParserResult {
+    ctx.mapping(|ctx| {
+        let name = ctx.next_scalar()?.0;
+        expect_token!(ctx.next()?, Value);
+        if !name.starts_with("sh[") {
+            return ctx.skip_next_value().map(Continue);
+        }
+        ctx.write_until_current_token()?;
+        ctx.skip_next_value()?;
+        log_replaced_value(
+            &format!("m_AmbientProbe.{}", name),
+            ctx.unprinted_until_current_token(),
+            "0",
+        );
+        ctx.append_str(" 0");
+        ctx.skip_until_current_token()?;
+        Ok(Continue(()))
+    })
+}
+
+/// logs the original value replaced by a rule if it's not the value expected to be computed
+/// so that users can find unintended data loss with debug log. returns true if logged.
+fn log_replaced_value(field: &str, original: &str, expected: &str) -> bool {
+    let original = original.trim();
+    if original == expected {
+        return false;
+    }
+    debug!("{}: replaced {} with {}", field, original, expected);
+    true
+}
+
 #[cfg(test)]
 mod test_generic {
     use super::*;
@@ -420,56 +1009,282 @@ mod test_generic {
         );
         Ok(())
     }
-}
-
-#[cfg(test)]
-mod test_udon_program_asset {
-    use super::*;
 
     #[test]
-    fn udon_program_asset() -> anyhow::Result<()> {
-        assert_eq!(filter_yaml(concat!(
-        "MonoBehaviour:\n",
-        "  m_ObjectHideFlags: 0\n",
-        "  m_CorrespondingSourceObject: {fileID: 0}\n",
-        "  m_PrefabInstance: {fileID: 0}\n",
-        "  m_PrefabAsset: {fileID: 0}\n",
-        "  m_GameObject: {fileID: 0}\n",
-        "  m_Enabled: 1\n",
-        "  m_EditorHideFlags: 0\n",
-        "  m_Script: {fileID: 11500000, guid: 22203902d63dec94194fefc3e155c43b, type: 3}\n",
-        "  m_Name: New Udon Assembly Program Asset\n",
-        "  m_EditorClassIdentifier:\n",
-        "  serializedUdonProgramAsset: {fileID: 11400000, guid: aa8a5233c74e54f108dfb136df564958,\n",
-        "    type: 2}\n",
-        "  udonAssembly:\n",
-        "  assemblyError:\n",
-        ))?, concat!(
-        "MonoBehaviour:\n",
-        "  m_ObjectHideFlags: 0\n",
-        "  m_CorrespondingSourceObject: {fileID: 0}\n",
-        "  m_PrefabInstance: {fileID: 0}\n",
-        "  m_PrefabAsset: {fileID: 0}\n",
-        "  m_GameObject: {fileID: 0}\n",
-        "  m_Enabled: 1\n",
-        "  m_EditorHideFlags: 0\n",
-        "  m_Script: {fileID: 11500000, guid: 22203902d63dec94194fefc3e155c43b, type: 3}\n",
-        "  m_Name: New Udon Assembly Program Asset\n",
-        "  m_EditorClassIdentifier:\n",
-        "  serializedUdonProgramAsset: {fileID: 0}\n",
-        "  udonAssembly:\n",
-        "  assemblyError:\n",
-        ));
+    fn deeply_nested_value() -> anyhow::Result<()> {
+        let mut yaml = String::new();
+        yaml.push_str("MonoBehaviour:\n");
+        yaml.push_str("  m_ObjectHideFlags: 0\n");
+        yaml.push_str("  m_Nested:\n");
+        for depth in 0..5000 {
+            yaml.push_str(&" ".repeat(depth + 3));
+            yaml.push_str("nested:\n");
+        }
+        yaml.push_str(&" ".repeat(5000 + 3));
+        yaml.push_str("value: 0\n");
+        yaml.push_str("  m_Enabled: 1\n");
+
+        assert_eq!(filter_yaml(&yaml)?, yaml.as_str());
         Ok(())
     }
-}
-
-#[cfg(test)]
-mod test_udon_behaviour {
-    use super::*;
 
     #[test]
-    fn mono_behaviour() -> anyhow::Result<()> {
+    fn already_empty_values() -> anyhow::Result<()> {
+        // replaced values are zero-width
+        assert_eq!(
+            filter_yaml(concat!(
+                "MonoBehaviour:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  serializedProgramAsset:\n",
+                "  fallbackStatus:\n",
+            ))?,
+            concat!(
+                "MonoBehaviour:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  serializedProgramAsset: {fileID: 0}\n",
+                "  fallbackStatus: 0\n",
+            ),
+        );
+        Ok(())
+    }
+    #[test]
+    fn flow_root() -> anyhow::Result<()> {
+        assert_eq!(
+            filter_yaml("MonoBehaviour: {m_ObjectHideFlags: 0, fallbackStatus: 3}\n")?,
+            "MonoBehaviour: {m_ObjectHideFlags: 0, fallbackStatus: 0}\n",
+        );
+        assert_eq!(
+            filter_yaml("{MonoBehaviour: {m_ObjectHideFlags: 0, fallbackStatus: 3}}\n")?,
+            "{MonoBehaviour: {m_ObjectHideFlags: 0, fallbackStatus: 0}}\n",
+        );
+        assert_eq!(
+            filter_yaml("{GameObject: {m_Name: Object}}\n")?,
+            "{GameObject: {m_Name: Object}}\n",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_object_type() -> anyhow::Result<()> {
+        // object types not known by this tool must be passed through as is
+        let configs = [
+            FilterConfig::default(),
+            FilterConfig {
+                normalize_negative_zero: true,
+                trim_name: true,
+                ..Default::default()
+            },
+            FilterConfig {
+                objects_only: true,
+                ..Default::default()
+            },
+        ];
+        for object_type in [
+            "Future Type",
+            "\"Type: With Colon\"",
+            "'MonoBehaviour '",
+            "MonoBehaviour2",
+            "Name.Space.Type<T>",
+            "タイプ",
+        ] {
+            let yaml = format!(
+                "{}:\n  m_ObjectHideFlags: 0\n  fallbackStatus: 3\n  m_Name: 'Name '\n",
+                object_type,
+            );
+            for config in &configs {
+                assert_eq!(filter_yaml_with_config(&yaml, config)?, yaml);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn escaped_newline_in_double_quoted() -> anyhow::Result<()> {
+        assert_eq!(
+            filter_yaml(concat!(
+                "MonoBehaviour:\n",
+                "  m_Name: \"First\\nSecond\\r\\n\\u3042\"\n",
+                "  m_EditorClassIdentifier: \"multi\n",
+                "    line \\n value\"\n",
+                "  fallbackStatus: 3\n",
+            ))?,
+            concat!(
+                "MonoBehaviour:\n",
+                "  m_Name: \"First\\nSecond\\r\\n\\u3042\"\n",
+                "  m_EditorClassIdentifier: \"multi\n",
+                "    line \\n value\"\n",
+                "  fallbackStatus: 0\n",
+            ),
+        );
+        let config = FilterConfig {
+            trim_name: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            filter_yaml_with_config("MonoBehaviour:\n  m_Name: \"First\\nSecond  \"\n", &config)?,
+            "MonoBehaviour:\n  m_Name: \"First\\nSecond\"\n",
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_prefab_linkage {
+    use super::*;
+
+    #[test]
+    fn mono_behaviour() -> anyhow::Result<()> {
+        assert_eq!(
+            filter_yaml(concat!(
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  fallbackStatus: 3\n",
+            "  m_CorrespondingSourceObject: {fileID: 973945594870973798, guid: 27c023e317f775c45aca5b55f6eab077,\n",
+            "    type: 3}\n",
+            "  m_PrefabInstance: {fileID: 1414016467}\n",
+            "  completedSDKPipeline: 1\n",
+            "  m_PrefabAsset: {fileID: 0}\n",
+            "  m_GameObject: {fileID: 973945594870973796}\n",
+            "  m_Enabled: 1\n",
+            "  m_EditorHideFlags: 0\n",
+            "  m_Script: {fileID: -1427037861, guid: 4ecd63eff847044b68db9453ce219299, type: 3}\n",
+            "  m_Name: \n",
+            "  m_EditorClassIdentifier: \n",
+            ))?,
+            concat!(
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  fallbackStatus: 0\n",
+            "  m_CorrespondingSourceObject: {fileID: 973945594870973798, guid: 27c023e317f775c45aca5b55f6eab077,\n",
+            "    type: 3}\n",
+            "  m_PrefabInstance: {fileID: 1414016467}\n",
+            "  completedSDKPipeline: 0\n",
+            "  m_PrefabAsset: {fileID: 0}\n",
+            "  m_GameObject: {fileID: 973945594870973796}\n",
+            "  m_Enabled: 1\n",
+            "  m_EditorHideFlags: 0\n",
+            "  m_Script: {fileID: -1427037861, guid: 4ecd63eff847044b68db9453ce219299, type: 3}\n",
+            "  m_Name: \n",
+            "  m_EditorClassIdentifier: \n",
+            ),
+        );
+        Ok(())
+    }
+
+    // linkage to prefab must be kept as is with any rules
+    fn all_rules() -> FilterConfig {
+        FilterConfig {
+            indirect_specular_color_tolerance: None,
+            normalize_editor_class_identifier: true,
+            normalize_view_position: true,
+            normalize_negative_zero: true,
+            trim_name: true,
+            objects_only: false,
+            remove_orphan_array_size: true,
+            normalize_default_animator_controller: true,
+            normalize_auto_colliders: true,
+            normalize_ambient_probe: true,
+            sort_removed_components: true,
+            normalize_station_null_references: true,
+        }
+    }
+
+    const LINKAGE: &str = concat!(
+        "  m_CorrespondingSourceObject: {fileID: 100100000, guid: 0123456789abcdef0123456789abcdef, type: 3}\n",
+        "  m_PrefabInstance: {fileID: 1234567890}\n",
+        "  m_PrefabAsset: {fileID: 100100000, guid: 0123456789abcdef0123456789abcdef, type: 3}\n",
+    );
+
+    #[test]
+    fn non_zero_linkage() -> anyhow::Result<()> {
+        for (object_type, fields) in [
+            (
+                "MonoBehaviour",
+                concat!(
+                    "  m_GameObject: {fileID: 1}\n",
+                    "  m_Script: {fileID: 11500000, guid: 0123456789abcdef0123456789abcdef, type: 3}\n",
+                    "  m_Name: \n",
+                ),
+            ),
+            (
+                "GameObject",
+                "  serializedVersion: 6\n  m_Name: 'Object '\n",
+            ),
+            (
+                "Transform",
+                "  m_LocalPosition: {x: 0, y: 0, z: 0}\n",
+            ),
+        ] {
+            let yaml = format!("{}:\n  m_ObjectHideFlags: 0\n{}{}", object_type, LINKAGE, fields);
+            let filtered = filter_yaml_with_config(&yaml, &all_rules())?;
+            assert!(filtered.contains(LINKAGE), "{}", filtered);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn zero_linkage() -> anyhow::Result<()> {
+        let yaml = concat!(
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_CorrespondingSourceObject: {fileID: 0}\n",
+            "  m_PrefabInstance: {fileID: 0}\n",
+            "  m_PrefabAsset: {fileID: 0}\n",
+            "  m_GameObject: {fileID: 1}\n",
+        );
+        assert_eq!(filter_yaml_with_config(yaml, &all_rules())?, yaml);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_udon_program_asset {
+    use super::*;
+
+    #[test]
+    fn udon_program_asset() -> anyhow::Result<()> {
+        assert_eq!(filter_yaml(concat!(
+        "MonoBehaviour:\n",
+        "  m_ObjectHideFlags: 0\n",
+        "  m_CorrespondingSourceObject: {fileID: 0}\n",
+        "  m_PrefabInstance: {fileID: 0}\n",
+        "  m_PrefabAsset: {fileID: 0}\n",
+        "  m_GameObject: {fileID: 0}\n",
+        "  m_Enabled: 1\n",
+        "  m_EditorHideFlags: 0\n",
+        "  m_Script: {fileID: 11500000, guid: 22203902d63dec94194fefc3e155c43b, type: 3}\n",
+        "  m_Name: New Udon Assembly Program Asset\n",
+        "  m_EditorClassIdentifier:\n",
+        "  serializedUdonProgramAsset: {fileID: 11400000, guid: aa8a5233c74e54f108dfb136df564958,\n",
+        "    type: 2}\n",
+        "  udonAssembly:\n",
+        "  assemblyError:\n",
+        ))?, concat!(
+        "MonoBehaviour:\n",
+        "  m_ObjectHideFlags: 0\n",
+        "  m_CorrespondingSourceObject: {fileID: 0}\n",
+        "  m_PrefabInstance: {fileID: 0}\n",
+        "  m_PrefabAsset: {fileID: 0}\n",
+        "  m_GameObject: {fileID: 0}\n",
+        "  m_Enabled: 1\n",
+        "  m_EditorHideFlags: 0\n",
+        "  m_Script: {fileID: 11500000, guid: 22203902d63dec94194fefc3e155c43b, type: 3}\n",
+        "  m_Name: New Udon Assembly Program Asset\n",
+        "  m_EditorClassIdentifier:\n",
+        "  serializedUdonProgramAsset: {fileID: 0}\n",
+        "  udonAssembly:\n",
+        "  assemblyError:\n",
+        ));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_udon_behaviour {
+    use super::*;
+
+    #[test]
+    fn mono_behaviour() -> anyhow::Result<()> {
         assert_eq!(filter_yaml(concat!(
         "MonoBehaviour:\n",
         "  m_ObjectHideFlags: 2\n",
@@ -714,70 +1529,9 @@ mod test_prefab_modifications {
         );
         Ok(())
     }
-}
-
-#[cfg(test)]
-mod test_dynamic_materials_and_prefab {
-    use super::*;
-    // see https://github.com/anatawa12/git-vrc/issues/5
-
-    #[test]
-    fn mono_behaviour() -> anyhow::Result<()> {
-        assert_eq!(
-            filter_yaml(concat!(
-                "MonoBehaviour:\n",
-                // many fields omitted
-                "  useAssignedLayers: 0\n",
-                "  DynamicPrefabs: \n",
-                "  - {fileID: 2100000, guid: 3f13a5d1eb038764b804d1aabffed55f, type: 2}\n",
-                "  - {fileID: 2100000, guid: 48f32ce8d7140f045a2c568df3a8d9bd, type: 2}\n",
-                "  - {fileID: 2100000, guid: 09418b03dc9fc469f8d23aca7b180691, type: 2}\n",
-                "  - {fileID: 2100000, guid: 43d0ae848fdfe6d4495a87f8e80e386b, type: 2}\n",
-                "  - {fileID: 2100000, guid: c2af845bdfb561149b08ba13167ff040, type: 2}\n",
-                "  - {fileID: 2180264, guid: 8f586378b4e144a9851e7b34d9b748ee, type: 2}\n",
-                "  DynamicMaterials:\n",
-                "  - {fileID: 2100000, guid: 3f13a5d1eb038764b804d1aabffed55f, type: 2}\n",
-                "  - {fileID: 2100000, guid: 48f32ce8d7140f045a2c568df3a8d9bd, type: 2}\n",
-                "  - {fileID: 2100000, guid: 09418b03dc9fc469f8d23aca7b180691, type: 2}\n",
-                "  - {fileID: 2100000, guid: 43d0ae848fdfe6d4495a87f8e80e386b, type: 2}\n",
-                "  - {fileID: 2100000, guid: c2af845bdfb561149b08ba13167ff040, type: 2}\n",
-                "  - {fileID: 2180264, guid: 8f586378b4e144a9851e7b34d9b748ee, type: 2}\n",
-                "  - {fileID: 2100000, guid: a59b4d20f3b324ca1aae5fd4f3942cf3, type: 2}\n",
-                "  - {fileID: 2100000, guid: 9db9f48f3ee803d448488d4368a140f9, type: 2}\n",
-                "  - {fileID: 2100000, guid: dd75a5d3bd47a0c489c0fd71aff39ede, type: 2}\n",
-                "  - {fileID: 2100000, guid: 88aa935393607b6409baa45499f5156b, type: 2}\n",
-                "  - {fileID: 2100000, guid: a393dafb2990e2c4fa0628ace4444efa, type: 2}\n",
-                "  - {fileID: 2100000, guid: b24ed807dd7dc224baf5390f46738647, type: 2}\n",
-                "  - {fileID: 2100000, guid: 254a177cd9c57e84683d0fd3bd1be46d, type: 2}\n",
-                "  - {fileID: 10303, guid: 0000000000000000f000000000000000, type: 0}\n",
-                "  - {fileID: 2100000, guid: e01134920adbcf549ac7f52ceeb583a2, type: 2}\n",
-                "  - {fileID: 2100000, guid: 885a01c79ffd5024489a1fb31f3fffb5, type: 2}\n",
-                "  - {fileID: 2100000, guid: 87529c80faca0ef4a881efba652815f3, type: 2}\n",
-                "  - {fileID: 2100000, guid: 49c7ed6d767622b4fadcf200017fd44f, type: 2}\n",
-                "  - {fileID: 2100000, guid: e86e7281176dae945bd655f34805ed55, type: 2}\n",
-                "  - {fileID: 2100000, guid: 51d72acecdb1ba249957953415f8e29b, type: 2}\n",
-                "  - {fileID: 2100000, guid: 419ae9fed5372564c995339c60fd7ebf, type: 2}\n",
-                "  - {fileID: 2100000, guid: b3889ddf2a4bd9346a4843eb47e0acb1, type: 2}\n",
-                "  - {fileID: 2100000, guid: 56778de2f4060f14fb06bc8cba7e30b7, type: 2}\n",
-                "  - {fileID: 2100000, guid: 5b91c5c74862dba4d9fc2e8ae3e07b70, type: 2}\n",
-                "  LightMapsNear: []\n",
-                // many fields omitted
-            ))?,
-            concat!(
-                "MonoBehaviour:\n",
-                // many fields omitted
-                "  useAssignedLayers: 0\n",
-                "  DynamicPrefabs: []\n",
-                "  DynamicMaterials: []\n",
-                "  LightMapsNear: []\n",
-                // many fields omitted
-            ),
-        );
-        Ok(())
-    }
 
     #[test]
-    fn prefab() -> anyhow::Result<()> {
+    fn value_looks_like_flow_mapping() -> anyhow::Result<()> {
         assert_eq!(
             filter_yaml(concat!(
             "PrefabInstance:\n",
@@ -786,48 +1540,23 @@ mod test_dynamic_materials_and_prefab {
             "  m_Modification:\n",
             "    m_TransformParent: {fileID: 0}\n",
             "    m_Modifications:\n",
-            "    - target: {fileID: 6759095419728963412, guid: 8894fa7e4588a5c4fab98453e558847d,\n",
+            "    - target: {fileID: 690848371401817423, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
             "        type: 3}\n",
-            "      propertyPath: DynamicMaterials.Array.size\n",
-            "      value: 3\n",
+            "      propertyPath: m_Name\n",
+            "      value: '{not: a, real: map}'\n",
             "      objectReference: {fileID: 0}\n",
-            "    - target: {fileID: 6759095419728963412, guid: 8894fa7e4588a5c4fab98453e558847d,\n",
-            "        type: 3}\n",
-            "      propertyPath: DynamicMaterials.Array.data[0]\n",
-            "      value: \n",
-            "      objectReference: {fileID: 2100000, guid: 3e749d8edb4501f488bf37401bec19cf, type: 2}\n",
-            "    - target: {fileID: 6759095419728963412, guid: 8894fa7e4588a5c4fab98453e558847d,\n",
-            "        type: 3}\n",
-            "      propertyPath: DynamicMaterials.Array.data[1]\n",
-            "      value: \n",
-            "      objectReference: {fileID: 10303, guid: 0000000000000000f000000000000000, type: 0}\n",
-            "    - target: {fileID: 6759095419728963412, guid: 8894fa7e4588a5c4fab98453e558847d,\n",
-            "        type: 3}\n",
-            "      propertyPath: DynamicMaterials.Array.data[2]\n",
-            "      value: \n",
-            "      objectReference: {fileID: 10308, guid: 0000000000000000f000000000000000, type: 0}\n",
-            "    - target: {fileID: 6759095419728963412, guid: 8894fa7e4588a5c4fab98453e558847d,\n",
+            "    - target: {fileID: 9122363655180540528, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
             "        type: 3}\n",
-            "      propertyPath: DynamicPrefabs.Array.size\n",
-            "      value: 3\n",
+            "      propertyPath: serializedProgramAsset\n",
+            "      value: {not: a, real: map}\n",
             "      objectReference: {fileID: 0}\n",
-            "    - target: {fileID: 6759095419728963412, guid: 8894fa7e4588a5c4fab98453e558847d,\n",
-            "        type: 3}\n",
-            "      propertyPath: DynamicPrefabs.Array.data[0]\n",
-            "      value: \n",
-            "      objectReference: {fileID: 2100000, guid: 3e749d8edb4501f488bf37401bec19cf, type: 2}\n",
-            "    - target: {fileID: 6759095419728963412, guid: 8894fa7e4588a5c4fab98453e558847d,\n",
-            "        type: 3}\n",
-            "      propertyPath: DynamicPrefabs.Array.data[1]\n",
-            "      value: \n",
-            "      objectReference: {fileID: 10303, guid: 0000000000000000f000000000000000, type: 0}\n",
-            "    - target: {fileID: 6759095419728963412, guid: 8894fa7e4588a5c4fab98453e558847d,\n",
+            "    - target: {fileID: 9122363655180540528, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
             "        type: 3}\n",
-            "      propertyPath: DynamicPrefabs.Array.data[2]\n",
-            "      value: \n",
-            "      objectReference: {fileID: 10308, guid: 0000000000000000f000000000000000, type: 0}\n",
+            "      propertyPath: serializedProgramAsset\n",
+            "      value: [not, a, real, sequence]\n",
+            "      objectReference: {fileID: 0}\n",
             "    m_RemovedComponents: []\n",
-            "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
             ))?,
             concat!(
             "PrefabInstance:\n",
@@ -835,212 +1564,996 @@ mod test_dynamic_materials_and_prefab {
             "  serializedVersion: 2\n",
             "  m_Modification:\n",
             "    m_TransformParent: {fileID: 0}\n",
-            "    m_Modifications: []\n",
-            "    m_RemovedComponents: []\n",
-            "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
-            ),
+            "    m_Modifications:\n",
+            "    - target: {fileID: 690848371401817423, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
+            "        type: 3}\n",
+            "      propertyPath: m_Name\n",
+            "      value: '{not: a, real: map}'\n",
+            "      objectReference: {fileID: 0}\n",
+            "    - target: {fileID: 9122363655180540528, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
+            "        type: 3}\n",
+            "      propertyPath: serializedProgramAsset\n",
+            "      value: {not: a, real: map}\n",
+            "      objectReference: {fileID: 0}\n",
+            "    - target: {fileID: 9122363655180540528, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
+            "        type: 3}\n",
+            "      propertyPath: serializedProgramAsset\n",
+            "      value: [not, a, real, sequence]\n",
+            "      objectReference: {fileID: 0}\n",
+            "    m_RemovedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+            )
         );
         Ok(())
     }
-}
-
-#[cfg(test)]
-mod test_render_settings {
-    use super::*;
-    // see https://github.com/anatawa12/git-vrc/issues/5
 
     #[test]
-    fn mono_behaviour() -> anyhow::Result<()> {
+    fn without_source_prefab() -> anyhow::Result<()> {
         assert_eq!(
             filter_yaml(concat!(
-            "RenderSettings:\n",
+            "PrefabInstance:\n",
             "  m_ObjectHideFlags: 0\n",
-            "  serializedVersion: 9\n",
-            "  m_Fog: 0\n",
-            "  m_FogColor: {r: 0, g: 0, b: 0, a: 1}\n",
-            "  m_FogMode: 3\n",
-            "  m_FogDensity: 0.01\n",
-            "  m_LinearFogStart: 0\n",
-            "  m_LinearFogEnd: 300\n",
-            "  m_AmbientSkyColor: {r: 0, g: 0, b: 0, a: 1}\n",
-            "  m_AmbientEquatorColor: {r: 0, g: 0, b: 0, a: 1}\n",
-            "  m_AmbientGroundColor: {r: 0, g: 0, b: 0, a: 1}\n",
-            "  m_AmbientIntensity: 1\n",
-            "  m_AmbientMode: 0\n",
-            "  m_SubtractiveShadowColor: {r: 0, g: 0, b: 0, a: 1}\n",
-            "  m_SkyboxMaterial: {fileID: 10304, guid: 0000000000000000f000000000000000, type: 0}\n",
-            "  m_HaloStrength: 0.5\n",
-            "  m_FlareStrength: 1\n",
-            "  m_FlareFadeSpeed: 3\n",
-            "  m_HaloTexture: {fileID: 0}\n",
-            "  m_SpotCookie: {fileID: 10001, guid: 0000000000000000e000000000000000, type: 0}\n",
-            "  m_DefaultReflectionMode: 0\n",
-            "  m_DefaultReflectionResolution: 128\n",
-            "  m_ReflectionBounces: 1\n",
-            "  m_ReflectionIntensity: 1\n",
-            "  m_CustomReflection: {fileID: 0}\n",
-            "  m_Sun: {fileID: 0}\n",
-            "  m_IndirectSpecularColor: {r: 0.18028305, g: 0.22571313, b: 0.3069213, a: 1}\n",
-            "  m_UseRadianceAmbientProbe: 0\n",
-            // many fields omitted
+            "  serializedVersion: 2\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications:\n",
+            "    - target: {fileID: 690848371401817423, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
+            "        type: 3}\n",
+            "      propertyPath: m_Name\n",
+            "      value: GameObject\n",
+            "      objectReference: {fileID: 0}\n",
+            "    - target: {fileID: 9122363655180540528, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
+            "        type: 3}\n",
+            "      propertyPath: fallbackStatus\n",
+            "      value: 3\n",
+            "      objectReference: {fileID: 0}\n",
+            "    m_RemovedComponents: []\n",
             ))?,
             concat!(
-            "RenderSettings:\n",
+            "PrefabInstance:\n",
             "  m_ObjectHideFlags: 0\n",
-            "  serializedVersion: 9\n",
-            "  m_Fog: 0\n",
-            "  m_FogColor: {r: 0, g: 0, b: 0, a: 1}\n",
-            "  m_FogMode: 3\n",
-            "  m_FogDensity: 0.01\n",
-            "  m_LinearFogStart: 0\n",
-            "  m_LinearFogEnd: 300\n",
-            "  m_AmbientSkyColor: {r: 0, g: 0, b: 0, a: 1}\n",
-            "  m_AmbientEquatorColor: {r: 0, g: 0, b: 0, a: 1}\n",
-            "  m_AmbientGroundColor: {r: 0, g: 0, b: 0, a: 1}\n",
-            "  m_AmbientIntensity: 1\n",
-            "  m_AmbientMode: 0\n",
-            "  m_SubtractiveShadowColor: {r: 0, g: 0, b: 0, a: 1}\n",
-            "  m_SkyboxMaterial: {fileID: 10304, guid: 0000000000000000f000000000000000, type: 0}\n",
-            "  m_HaloStrength: 0.5\n",
-            "  m_FlareStrength: 1\n",
-            "  m_FlareFadeSpeed: 3\n",
-            "  m_HaloTexture: {fileID: 0}\n",
-            "  m_SpotCookie: {fileID: 10001, guid: 0000000000000000e000000000000000, type: 0}\n",
-            "  m_DefaultReflectionMode: 0\n",
-            "  m_DefaultReflectionResolution: 128\n",
-            "  m_ReflectionBounces: 1\n",
-            "  m_ReflectionIntensity: 1\n",
-            "  m_CustomReflection: {fileID: 0}\n",
-            "  m_Sun: {fileID: 0}\n",
-            "  m_IndirectSpecularColor: {r: 0, g: 0, b: 0, a: 1}\n",
-            "  m_UseRadianceAmbientProbe: 0\n",
-            ),
+            "  serializedVersion: 2\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications:\n",
+            "    - target: {fileID: 690848371401817423, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
+            "        type: 3}\n",
+            "      propertyPath: m_Name\n",
+            "      value: GameObject\n",
+            "      objectReference: {fileID: 0}\n",
+            "    m_RemovedComponents: []\n",
+            )
         );
         Ok(())
     }
-}
-
-#[cfg(test)]
-mod test_fallback_status {
-    use super::*;
-    // see https://github.com/anatawa12/git-vrc/issues/14
 
     #[test]
-    fn mono_behaviour() -> anyhow::Result<()> {
+    fn empty_and_null_value() -> anyhow::Result<()> {
         assert_eq!(
             filter_yaml(concat!(
-            "MonoBehaviour:\n",
+            "PrefabInstance:\n",
             "  m_ObjectHideFlags: 0\n",
-            "  m_CorrespondingSourceObject: {fileID: 0}\n",
-            "  m_PrefabInstance: {fileID: 0}\n",
-            "  m_PrefabAsset: {fileID: 0}\n",
-            "  m_GameObject: {fileID: 973945594870973796}\n",
-            "  m_Enabled: 1\n",
-            "  m_EditorHideFlags: 0\n",
-            "  m_Script: {fileID: -1427037861, guid: 4ecd63eff847044b68db9453ce219299, type: 3}\n",
-            "  m_Name: \n",
-            "  m_EditorClassIdentifier: \n",
-            "  launchedFromSDKPipeline: 0\n",
-            "  completedSDKPipeline: 0\n",
-            "  blueprintId: \n",
-            "  contentType: 0\n",
-            "  assetBundleUnityVersion: \n",
-            "  fallbackStatus: 3\n",
+            "  serializedVersion: 2\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications:\n",
+            "    - target: {fileID: 9122363655180540528, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
+            "        type: 3}\n",
+            "      propertyPath: serializedProgramAsset\n",
+            "      value:\n",
+            "      objectReference: {fileID: 0}\n",
+            "    - target: {fileID: 9122363655180540528, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
+            "        type: 3}\n",
+            "      propertyPath: serializedProgramAsset\n",
+            "      value: ~\n",
+            "      objectReference: {fileID: 0}\n",
+            "    - target: {fileID: 9122363655180540528, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
+            "        type: 3}\n",
+            "      propertyPath: serializedProgramAsset\n",
+            "      value: '~'\n",
+            "      objectReference: {fileID: 0}\n",
+            "    m_RemovedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
             ))?,
             concat!(
-            "MonoBehaviour:\n",
-            "  m_ObjectHideFlags: 0\n",
-            "  m_CorrespondingSourceObject: {fileID: 0}\n",
-            "  m_PrefabInstance: {fileID: 0}\n",
-            "  m_PrefabAsset: {fileID: 0}\n",
-            "  m_GameObject: {fileID: 973945594870973796}\n",
-            "  m_Enabled: 1\n",
-            "  m_EditorHideFlags: 0\n",
-            "  m_Script: {fileID: -1427037861, guid: 4ecd63eff847044b68db9453ce219299, type: 3}\n",
-            "  m_Name: \n",
-            "  m_EditorClassIdentifier: \n",
-            "  launchedFromSDKPipeline: 0\n",
-            "  completedSDKPipeline: 0\n",
-            "  blueprintId: \n",
-            "  contentType: 0\n",
-            "  assetBundleUnityVersion: \n",
-            "  fallbackStatus: 0\n",
-            ),
-        );
-        Ok(())
-    }
-
-    #[test]
-    fn prefab() -> anyhow::Result<()> {
-        assert_eq!(
-            filter_yaml(concat!(
             "PrefabInstance:\n",
             "  m_ObjectHideFlags: 0\n",
             "  serializedVersion: 2\n",
             "  m_Modification:\n",
             "    m_TransformParent: {fileID: 0}\n",
             "    m_Modifications:\n",
-            "    - target: {fileID: 973945594870973798, guid: 27c023e317f775c45aca5b55f6eab077,\n",
+            "    - target: {fileID: 9122363655180540528, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
             "        type: 3}\n",
-            "      propertyPath: fallbackStatus\n",
-            "      value: 3\n",
+            "      propertyPath: serializedProgramAsset\n",
+            "      value: '~'\n",
             "      objectReference: {fileID: 0}\n",
             "    m_RemovedComponents: []\n",
-            "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
-            ))?,
-            concat!(
+            "  m_SourcePrefab: {fileID: 100100000, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn empty_removed_components() -> anyhow::Result<()> {
+        for removed_components in ["[]", "[ ]", "~", "[\n    ]"] {
+            assert_eq!(
+                filter_yaml(&format!(
+                    concat!(
+                        "PrefabInstance:\n",
+                        "  m_ObjectHideFlags: 0\n",
+                        "  serializedVersion: 2\n",
+                        "  m_Modification:\n",
+                        "    m_TransformParent: {{fileID: 0}}\n",
+                        "    m_Modifications: []\n",
+                        "    m_RemovedComponents: {}\n",
+                        "  m_SourcePrefab: {{fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}}\n",
+                    ),
+                    removed_components
+                ))?,
+                concat!(
+                    "PrefabInstance:\n",
+                    "  m_ObjectHideFlags: 0\n",
+                    "  serializedVersion: 2\n",
+                    "  m_Modification:\n",
+                    "    m_TransformParent: {fileID: 0}\n",
+                    "    m_Modifications: []\n",
+                    "    m_RemovedComponents: []\n",
+                    "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+                ),
+                "{}",
+                removed_components
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn removed_components_kept() -> anyhow::Result<()> {
+        let yaml = concat!(
             "PrefabInstance:\n",
             "  m_ObjectHideFlags: 0\n",
             "  serializedVersion: 2\n",
             "  m_Modification:\n",
             "    m_TransformParent: {fileID: 0}\n",
             "    m_Modifications: []\n",
-            "    m_RemovedComponents: []\n",
+            "    m_RemovedComponents:\n",
+            "    - {fileID: 2087762956, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
             "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
-            ),
         );
+        assert_eq!(filter_yaml(yaml)?, yaml);
+        let yaml = yaml.replace(
+            "\n    - {fileID: 2087762956, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+            " [{fileID: 2087762956, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}]\n",
+        );
+        assert_eq!(filter_yaml(&yaml)?, yaml);
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_key() -> anyhow::Result<()> {
+        // modifications with unknown keys are kept and others are cleaned as usual
+        let modifications = |omitted: bool| {
+            format!(
+                concat!(
+                    "PrefabInstance:\n",
+                    "  m_ObjectHideFlags: 0\n",
+                    "  serializedVersion: 2\n",
+                    "  m_Modification:\n",
+                    "    m_TransformParent: {{fileID: 0}}\n",
+                    "    m_Modifications:\n",
+                    "    - target: {{fileID: 1, guid: 27c023e317f775c45aca5b55f6eab077, type: 3}}\n",
+                    "      propertyPath: fallbackStatus\n",
+                    "      value: 3\n",
+                    "      m_FutureKey: {{x: 1}}\n",
+                    "      objectReference: {{fileID: 0}}\n",
+                    "{}",
+                    "    m_RemovedComponents: []\n",
+                    "  m_SourcePrefab: {{fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}}\n",
+                ),
+                if omitted {
+                    ""
+                } else {
+                    // keys in unexpected order
+                    concat!(
+                        "    - objectReference: {fileID: 0}\n",
+                        "      value: 3\n",
+                        "      propertyPath: fallbackStatus\n",
+                        "      target: {fileID: 1, guid: 27c023e317f775c45aca5b55f6eab077, type: 3}\n",
+                    )
+                },
+            )
+        };
+        assert_eq!(filter_yaml(&modifications(false))?, modifications(true));
+        Ok(())
+    }
+
+    #[test]
+    fn stripped_prefab_instance() -> anyhow::Result<()> {
+        // stripped PrefabInstance may have no m_Modification
+        for yaml in [
+            "PrefabInstance:\n  m_ObjectHideFlags: 0\n  m_PrefabAsset: {fileID: 0}\n",
+            "PrefabInstance: {}\n",
+            "PrefabInstance:\n",
+            "PrefabInstance: ~\n",
+        ] {
+            assert_eq!(filter_yaml(yaml)?, yaml);
+        }
+        Ok(())
+    }
+
+    fn removed_components(references: [&str; 3]) -> String {
+        format!(
+            concat!(
+                "PrefabInstance:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  serializedVersion: 2\n",
+                "  m_Modification:\n",
+                "    m_TransformParent: {{fileID: 0}}\n",
+                "    m_Modifications: []\n",
+                "    m_RemovedComponents:\n",
+                "    - {}\n",
+                "    - {}\n",
+                "    - {}\n",
+                "  m_SourcePrefab: {{fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}}\n",
+            ),
+            references[0], references[1], references[2],
+        )
+    }
+
+    #[test]
+    fn sorted_removed_components() -> anyhow::Result<()> {
+        let config = FilterConfig {
+            sort_removed_components: true,
+            ..Default::default()
+        };
+        let a = "{fileID: -3, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}";
+        let b = "{fileID: 1234, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}";
+        let c = "{fileID: 2087762956, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}";
+        let sorted = removed_components([a, b, c]);
+
+        // unsorted ones are sorted
+        let yaml = removed_components([c, a, b]);
+        assert_eq!(filter_yaml_with_config(&yaml, &config)?, sorted);
+        // kept if the rule is disabled
+        assert_eq!(filter_yaml(&yaml)?, yaml);
+        // sorted ones are kept as is
+        assert_eq!(filter_yaml_with_config(&sorted, &config)?, sorted);
         Ok(())
     }
 }
 
 #[cfg(test)]
-mod test_animation_hash_set {
+mod test_dynamic_materials_and_prefab {
     use super::*;
-    // see https://github.com/anatawa12/git-vrc/issues/13
+    // see https://github.com/anatawa12/git-vrc/issues/5
 
     #[test]
     fn mono_behaviour() -> anyhow::Result<()> {
-        // many fields are omitted
         assert_eq!(
             filter_yaml(concat!(
-            "MonoBehaviour:\n",
-            "  m_ObjectHideFlags: 0\n",
-            "  m_CorrespondingSourceObject: {fileID: 0}\n",
-            "  m_PrefabInstance: {fileID: 0}\n",
-            "  m_PrefabAsset: {fileID: 0}\n",
-            "  m_GameObject: {fileID: 973945594870973796}\n",
-            "  m_Enabled: 1\n",
-            "  m_EditorHideFlags: 0\n",
-            "  m_Script: {fileID: 542108242, guid: 67cc4cb7839cd3741b63733d5adf0442, type: 3}\n",
-            "  m_Name:\n",
-            "  m_EditorClassIdentifier: \n",
-            "  AnimationPreset: {fileID: 0}\n",
-            "  animationHashSet:\n",
-            "  - hash: 1127303507\n",
-            "    name: Prone\n",
-            "  - hash: -510014593\n",
-            "    name: Standing\n",
-            "  - hash: 1277114970\n",
-            "    name: Crouching\n",
-            "  - hash: -946967196\n",
-            "    name: Supine\n",
-            "  - hash: -1752920240\n",
-            "    name: Left Side\n",
-            "  - hash: -1868992157\n",
-            "    name: Right Side\n",
-            "  - hash: 1325891751\n",
-            "    name: petan\n",
-            "  - hash: -1840526229\n",
+                "MonoBehaviour:\n",
+                // many fields omitted
+                "  useAssignedLayers: 0\n",
+                "  DynamicPrefabs: \n",
+                "  - {fileID: 2100000, guid: 3f13a5d1eb038764b804d1aabffed55f, type: 2}\n",
+                "  - {fileID: 2100000, guid: 48f32ce8d7140f045a2c568df3a8d9bd, type: 2}\n",
+                "  - {fileID: 2100000, guid: 09418b03dc9fc469f8d23aca7b180691, type: 2}\n",
+                "  - {fileID: 2100000, guid: 43d0ae848fdfe6d4495a87f8e80e386b, type: 2}\n",
+                "  - {fileID: 2100000, guid: c2af845bdfb561149b08ba13167ff040, type: 2}\n",
+                "  - {fileID: 2180264, guid: 8f586378b4e144a9851e7b34d9b748ee, type: 2}\n",
+                "  DynamicMaterials:\n",
+                "  - {fileID: 2100000, guid: 3f13a5d1eb038764b804d1aabffed55f, type: 2}\n",
+                "  - {fileID: 2100000, guid: 48f32ce8d7140f045a2c568df3a8d9bd, type: 2}\n",
+                "  - {fileID: 2100000, guid: 09418b03dc9fc469f8d23aca7b180691, type: 2}\n",
+                "  - {fileID: 2100000, guid: 43d0ae848fdfe6d4495a87f8e80e386b, type: 2}\n",
+                "  - {fileID: 2100000, guid: c2af845bdfb561149b08ba13167ff040, type: 2}\n",
+                "  - {fileID: 2180264, guid: 8f586378b4e144a9851e7b34d9b748ee, type: 2}\n",
+                "  - {fileID: 2100000, guid: a59b4d20f3b324ca1aae5fd4f3942cf3, type: 2}\n",
+                "  - {fileID: 2100000, guid: 9db9f48f3ee803d448488d4368a140f9, type: 2}\n",
+                "  - {fileID: 2100000, guid: dd75a5d3bd47a0c489c0fd71aff39ede, type: 2}\n",
+                "  - {fileID: 2100000, guid: 88aa935393607b6409baa45499f5156b, type: 2}\n",
+                "  - {fileID: 2100000, guid: a393dafb2990e2c4fa0628ace4444efa, type: 2}\n",
+                "  - {fileID: 2100000, guid: b24ed807dd7dc224baf5390f46738647, type: 2}\n",
+                "  - {fileID: 2100000, guid: 254a177cd9c57e84683d0fd3bd1be46d, type: 2}\n",
+                "  - {fileID: 10303, guid: 0000000000000000f000000000000000, type: 0}\n",
+                "  - {fileID: 2100000, guid: e01134920adbcf549ac7f52ceeb583a2, type: 2}\n",
+                "  - {fileID: 2100000, guid: 885a01c79ffd5024489a1fb31f3fffb5, type: 2}\n",
+                "  - {fileID: 2100000, guid: 87529c80faca0ef4a881efba652815f3, type: 2}\n",
+                "  - {fileID: 2100000, guid: 49c7ed6d767622b4fadcf200017fd44f, type: 2}\n",
+                "  - {fileID: 2100000, guid: e86e7281176dae945bd655f34805ed55, type: 2}\n",
+                "  - {fileID: 2100000, guid: 51d72acecdb1ba249957953415f8e29b, type: 2}\n",
+                "  - {fileID: 2100000, guid: 419ae9fed5372564c995339c60fd7ebf, type: 2}\n",
+                "  - {fileID: 2100000, guid: b3889ddf2a4bd9346a4843eb47e0acb1, type: 2}\n",
+                "  - {fileID: 2100000, guid: 56778de2f4060f14fb06bc8cba7e30b7, type: 2}\n",
+                "  - {fileID: 2100000, guid: 5b91c5c74862dba4d9fc2e8ae3e07b70, type: 2}\n",
+                "  LightMapsNear: []\n",
+                // many fields omitted
+            ))?,
+            concat!(
+                "MonoBehaviour:\n",
+                // many fields omitted
+                "  useAssignedLayers: 0\n",
+                "  DynamicPrefabs: []\n",
+                "  DynamicMaterials: []\n",
+                "  LightMapsNear: []\n",
+                // many fields omitted
+            ),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn already_empty() -> anyhow::Result<()> {
+        let yaml = concat!(
+            "MonoBehaviour:\n",
+            // many fields omitted
+            "  useAssignedLayers: 0\n",
+            "  DynamicPrefabs: []\n",
+            "  DynamicMaterials: []\n",
+            "  LightMapsNear: []\n",
+            "  animationHashSet: []\n",
+            // many fields omitted
+        );
+        assert_eq!(filter_yaml(yaml)?, yaml);
+        assert_eq!(filter_yaml(&filter_yaml(yaml)?)?, yaml);
+        Ok(())
+    }
+
+    #[test]
+    fn prefab() -> anyhow::Result<()> {
+        assert_eq!(
+            filter_yaml(concat!(
+            "PrefabInstance:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 2\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications:\n",
+            "    - target: {fileID: 6759095419728963412, guid: 8894fa7e4588a5c4fab98453e558847d,\n",
+            "        type: 3}\n",
+            "      propertyPath: DynamicMaterials.Array.size\n",
+            "      value: 3\n",
+            "      objectReference: {fileID: 0}\n",
+            "    - target: {fileID: 6759095419728963412, guid: 8894fa7e4588a5c4fab98453e558847d,\n",
+            "        type: 3}\n",
+            "      propertyPath: DynamicMaterials.Array.data[0]\n",
+            "      value: \n",
+            "      objectReference: {fileID: 2100000, guid: 3e749d8edb4501f488bf37401bec19cf, type: 2}\n",
+            "    - target: {fileID: 6759095419728963412, guid: 8894fa7e4588a5c4fab98453e558847d,\n",
+            "        type: 3}\n",
+            "      propertyPath: DynamicMaterials.Array.data[1]\n",
+            "      value: \n",
+            "      objectReference: {fileID: 10303, guid: 0000000000000000f000000000000000, type: 0}\n",
+            "    - target: {fileID: 6759095419728963412, guid: 8894fa7e4588a5c4fab98453e558847d,\n",
+            "        type: 3}\n",
+            "      propertyPath: DynamicMaterials.Array.data[2]\n",
+            "      value: \n",
+            "      objectReference: {fileID: 10308, guid: 0000000000000000f000000000000000, type: 0}\n",
+            "    - target: {fileID: 6759095419728963412, guid: 8894fa7e4588a5c4fab98453e558847d,\n",
+            "        type: 3}\n",
+            "      propertyPath: DynamicPrefabs.Array.size\n",
+            "      value: 3\n",
+            "      objectReference: {fileID: 0}\n",
+            "    - target: {fileID: 6759095419728963412, guid: 8894fa7e4588a5c4fab98453e558847d,\n",
+            "        type: 3}\n",
+            "      propertyPath: DynamicPrefabs.Array.data[0]\n",
+            "      value: \n",
+            "      objectReference: {fileID: 2100000, guid: 3e749d8edb4501f488bf37401bec19cf, type: 2}\n",
+            "    - target: {fileID: 6759095419728963412, guid: 8894fa7e4588a5c4fab98453e558847d,\n",
+            "        type: 3}\n",
+            "      propertyPath: DynamicPrefabs.Array.data[1]\n",
+            "      value: \n",
+            "      objectReference: {fileID: 10303, guid: 0000000000000000f000000000000000, type: 0}\n",
+            "    - target: {fileID: 6759095419728963412, guid: 8894fa7e4588a5c4fab98453e558847d,\n",
+            "        type: 3}\n",
+            "      propertyPath: DynamicPrefabs.Array.data[2]\n",
+            "      value: \n",
+            "      objectReference: {fileID: 10308, guid: 0000000000000000f000000000000000, type: 0}\n",
+            "    m_RemovedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+            ))?,
+            concat!(
+            "PrefabInstance:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 2\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications: []\n",
+            "    m_RemovedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+            ),
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_render_settings {
+    use super::*;
+    // see https://github.com/anatawa12/git-vrc/issues/5
+
+    #[test]
+    fn mono_behaviour() -> anyhow::Result<()> {
+        assert_eq!(
+            filter_yaml(concat!(
+            "RenderSettings:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 9\n",
+            "  m_Fog: 0\n",
+            "  m_FogColor: {r: 0, g: 0, b: 0, a: 1}\n",
+            "  m_FogMode: 3\n",
+            "  m_FogDensity: 0.01\n",
+            "  m_LinearFogStart: 0\n",
+            "  m_LinearFogEnd: 300\n",
+            "  m_AmbientSkyColor: {r: 0, g: 0, b: 0, a: 1}\n",
+            "  m_AmbientEquatorColor: {r: 0, g: 0, b: 0, a: 1}\n",
+            "  m_AmbientGroundColor: {r: 0, g: 0, b: 0, a: 1}\n",
+            "  m_AmbientIntensity: 1\n",
+            "  m_AmbientMode: 0\n",
+            "  m_SubtractiveShadowColor: {r: 0, g: 0, b: 0, a: 1}\n",
+            "  m_SkyboxMaterial: {fileID: 10304, guid: 0000000000000000f000000000000000, type: 0}\n",
+            "  m_HaloStrength: 0.5\n",
+            "  m_FlareStrength: 1\n",
+            "  m_FlareFadeSpeed: 3\n",
+            "  m_HaloTexture: {fileID: 0}\n",
+            "  m_SpotCookie: {fileID: 10001, guid: 0000000000000000e000000000000000, type: 0}\n",
+            "  m_DefaultReflectionMode: 0\n",
+            "  m_DefaultReflectionResolution: 128\n",
+            "  m_ReflectionBounces: 1\n",
+            "  m_ReflectionIntensity: 1\n",
+            "  m_CustomReflection: {fileID: 0}\n",
+            "  m_Sun: {fileID: 0}\n",
+            "  m_IndirectSpecularColor: {r: 0.18028305, g: 0.22571313, b: 0.3069213, a: 1}\n",
+            "  m_UseRadianceAmbientProbe: 0\n",
+            // many fields omitted
+            ))?,
+            concat!(
+            "RenderSettings:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 9\n",
+            "  m_Fog: 0\n",
+            "  m_FogColor: {r: 0, g: 0, b: 0, a: 1}\n",
+            "  m_FogMode: 3\n",
+            "  m_FogDensity: 0.01\n",
+            "  m_LinearFogStart: 0\n",
+            "  m_LinearFogEnd: 300\n",
+            "  m_AmbientSkyColor: {r: 0, g: 0, b: 0, a: 1}\n",
+            "  m_AmbientEquatorColor: {r: 0, g: 0, b: 0, a: 1}\n",
+            "  m_AmbientGroundColor: {r: 0, g: 0, b: 0, a: 1}\n",
+            "  m_AmbientIntensity: 1\n",
+            "  m_AmbientMode: 0\n",
+            "  m_SubtractiveShadowColor: {r: 0, g: 0, b: 0, a: 1}\n",
+            "  m_SkyboxMaterial: {fileID: 10304, guid: 0000000000000000f000000000000000, type: 0}\n",
+            "  m_HaloStrength: 0.5\n",
+            "  m_FlareStrength: 1\n",
+            "  m_FlareFadeSpeed: 3\n",
+            "  m_HaloTexture: {fileID: 0}\n",
+            "  m_SpotCookie: {fileID: 10001, guid: 0000000000000000e000000000000000, type: 0}\n",
+            "  m_DefaultReflectionMode: 0\n",
+            "  m_DefaultReflectionResolution: 128\n",
+            "  m_ReflectionBounces: 1\n",
+            "  m_ReflectionIntensity: 1\n",
+            "  m_CustomReflection: {fileID: 0}\n",
+            "  m_Sun: {fileID: 0}\n",
+            "  m_IndirectSpecularColor: {r: 0, g: 0, b: 0, a: 1}\n",
+            "  m_UseRadianceAmbientProbe: 0\n",
+            ),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn future_serialized_version() -> anyhow::Result<()> {
+        assert_eq!(
+            filter_yaml(concat!(
+                "RenderSettings:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  serializedVersion: 10\n",
+                "  m_Fog: 0\n",
+                "  m_FutureStruct:\n",
+                "    serializedVersion: 2\n",
+                "    m_Values:\n",
+                "    - {x: 1, y: 2}\n",
+                "    - - 1\n",
+                "      - 2\n",
+                "    m_Empty: []\n",
+                "  m_FutureFlow: [1, 2, 3]\n",
+                "  m_IndirectSpecularColor: {r: 0.18028305, g: 0.22571313, b: 0.3069213, a: 1}\n",
+                "  m_FutureColor: {r: 0.18028305, g: 0.22571313, b: 0.3069213, a: 1}\n",
+            ))?,
+            concat!(
+                "RenderSettings:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  serializedVersion: 10\n",
+                "  m_Fog: 0\n",
+                "  m_FutureStruct:\n",
+                "    serializedVersion: 2\n",
+                "    m_Values:\n",
+                "    - {x: 1, y: 2}\n",
+                "    - - 1\n",
+                "      - 2\n",
+                "    m_Empty: []\n",
+                "  m_FutureFlow: [1, 2, 3]\n",
+                "  m_IndirectSpecularColor: {r: 0, g: 0, b: 0, a: 1}\n",
+                "  m_FutureColor: {r: 0.18028305, g: 0.22571313, b: 0.3069213, a: 1}\n",
+            ),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn noisy_near_zero_with_tolerance() -> anyhow::Result<()> {
+        let config = FilterConfig {
+            indirect_specular_color_tolerance: Some(1e-5),
+            ..Default::default()
+        };
+        assert_eq!(
+            filter_yaml_with_config(
+                concat!(
+                    "RenderSettings:\n",
+                    "  m_ObjectHideFlags: 0\n",
+                    "  serializedVersion: 9\n",
+                    "  m_Sun: {fileID: 0}\n",
+                    "  m_IndirectSpecularColor: {r: 1e-7, g: -0.0000002, b: 0, a: 0.9999999}\n",
+                    "  m_UseRadianceAmbientProbe: 0\n",
+                ),
+                &config
+            )?,
+            concat!(
+                "RenderSettings:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  serializedVersion: 9\n",
+                "  m_Sun: {fileID: 0}\n",
+                "  m_IndirectSpecularColor: {r: 0, g: 0, b: 0, a: 1}\n",
+                "  m_UseRadianceAmbientProbe: 0\n",
+            ),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unparsable_color_without_tolerance() -> anyhow::Result<()> {
+        assert_eq!(
+            filter_yaml(concat!(
+                "RenderSettings:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  m_IndirectSpecularColor: {r: unknown, g: 0, b: 0}\n",
+                "  m_UseRadianceAmbientProbe: 0\n",
+            ))?,
+            concat!(
+                "RenderSettings:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  m_IndirectSpecularColor: {r: 0, g: 0, b: 0, a: 1}\n",
+                "  m_UseRadianceAmbientProbe: 0\n",
+            ),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn real_color_with_tolerance() -> anyhow::Result<()> {
+        let config = FilterConfig {
+            indirect_specular_color_tolerance: Some(1e-5),
+            ..Default::default()
+        };
+        assert_eq!(
+            filter_yaml_with_config(
+                concat!(
+                    "RenderSettings:\n",
+                    "  m_ObjectHideFlags: 0\n",
+                    "  serializedVersion: 9\n",
+                    "  m_Sun: {fileID: 0}\n",
+                    "  m_IndirectSpecularColor: {r: 0.18028305, g: 0.22571313, b: 0.3069213, a: 1}\n",
+                    "  m_UseRadianceAmbientProbe: 0\n",
+                ),
+                &config
+            )?,
+            concat!(
+                "RenderSettings:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  serializedVersion: 9\n",
+                "  m_Sun: {fileID: 0}\n",
+                "  m_IndirectSpecularColor: {r: 0.18028305, g: 0.22571313, b: 0.3069213, a: 1}\n",
+                "  m_UseRadianceAmbientProbe: 0\n",
+            ),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn prefab() -> anyhow::Result<()> {
+        assert_eq!(
+            filter_yaml(concat!(
+            "PrefabInstance:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 2\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications:\n",
+            "    - target: {fileID: 690848371401817423, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
+            "        type: 3}\n",
+            "      propertyPath: m_Name\n",
+            "      value: GameObject\n",
+            "      objectReference: {fileID: 0}\n",
+            "    - target: {fileID: 690848371401817424, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
+            "        type: 3}\n",
+            "      propertyPath: m_IndirectSpecularColor.r\n",
+            "      value: 0.18028361\n",
+            "      objectReference: {fileID: 0}\n",
+            "    - target: {fileID: 690848371401817424, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
+            "        type: 3}\n",
+            "      propertyPath: m_IndirectSpecularColor.a\n",
+            "      value: 1\n",
+            "      objectReference: {fileID: 0}\n",
+            "    m_RemovedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+            ))?,
+            concat!(
+            "PrefabInstance:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 2\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications:\n",
+            "    - target: {fileID: 690848371401817423, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
+            "        type: 3}\n",
+            "      propertyPath: m_Name\n",
+            "      value: GameObject\n",
+            "      objectReference: {fileID: 0}\n",
+            "    m_RemovedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+            ),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn prefab_with_tolerance() -> anyhow::Result<()> {
+        let config = FilterConfig {
+            indirect_specular_color_tolerance: Some(1e-5),
+            ..Default::default()
+        };
+        assert_eq!(
+            filter_yaml_with_config(
+                concat!(
+                "PrefabInstance:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  serializedVersion: 2\n",
+                "  m_Modification:\n",
+                "    m_TransformParent: {fileID: 0}\n",
+                "    m_Modifications:\n",
+                "    - target: {fileID: 690848371401817424, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
+                "        type: 3}\n",
+                "      propertyPath: m_IndirectSpecularColor.r\n",
+                "      value: 0.18028361\n",
+                "      objectReference: {fileID: 0}\n",
+                "    - target: {fileID: 690848371401817424, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
+                "        type: 3}\n",
+                "      propertyPath: m_IndirectSpecularColor.a\n",
+                "      value: 0.9999999\n",
+                "      objectReference: {fileID: 0}\n",
+                "    m_RemovedComponents: []\n",
+                "  m_SourcePrefab: {fileID: 100100000, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+                ),
+                &config
+            )?,
+            concat!(
+            "PrefabInstance:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 2\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications:\n",
+            "    - target: {fileID: 690848371401817424, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
+            "        type: 3}\n",
+            "      propertyPath: m_IndirectSpecularColor.r\n",
+            "      value: 0.18028361\n",
+            "      objectReference: {fileID: 0}\n",
+            "    m_RemovedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+            ),
+        );
+        Ok(())
+    }
+
+    fn ambient_probe(mode: &str, sh: [&str; 3]) -> String {
+        format!(
+            concat!(
+                "RenderSettings:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  serializedVersion: 9\n",
+                "  m_AmbientIntensity: 1\n",
+                "  m_AmbientMode: {}\n",
+                "  m_AmbientProbe:\n",
+                "    sh[ 0]: {}\n",
+                "    sh[ 1]: {}\n",
+                "    sh[26]: {}\n",
+                "  m_IndirectSpecularColor: {{r: 0, g: 0, b: 0, a: 1}}\n",
+                "  m_UseRadianceAmbientProbe: 0\n",
+            ),
+            mode, sh[0], sh[1], sh[2],
+        )
+    }
+
+    #[test]
+    fn ambient_probe_computed() -> anyhow::Result<()> {
+        let config = FilterConfig {
+            normalize_ambient_probe: true,
+            ..Default::default()
+        };
+        let yaml = ambient_probe("0", ["0.43213993", "-0.0021576", "0.1285343"]);
+        assert_eq!(
+            filter_yaml_with_config(&yaml, &config)?,
+            ambient_probe("0", ["0", "0", "0"]),
+        );
+        // kept if the rule is disabled
+        assert_eq!(filter_yaml(&yaml)?, yaml);
+        Ok(())
+    }
+
+    #[test]
+    fn ambient_probe_custom() -> anyhow::Result<()> {
+        let config = FilterConfig {
+            normalize_ambient_probe: true,
+            ..Default::default()
+        };
+        let yaml = ambient_probe("4", ["0.43213993", "-0.0021576", "0.1285343"]);
+        assert_eq!(filter_yaml_with_config(&yaml, &config)?, yaml);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_fallback_status {
+    use super::*;
+    // see https://github.com/anatawa12/git-vrc/issues/14
+
+    #[test]
+    fn mono_behaviour() -> anyhow::Result<()> {
+        assert_eq!(
+            filter_yaml(concat!(
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_CorrespondingSourceObject: {fileID: 0}\n",
+            "  m_PrefabInstance: {fileID: 0}\n",
+            "  m_PrefabAsset: {fileID: 0}\n",
+            "  m_GameObject: {fileID: 973945594870973796}\n",
+            "  m_Enabled: 1\n",
+            "  m_EditorHideFlags: 0\n",
+            "  m_Script: {fileID: -1427037861, guid: 4ecd63eff847044b68db9453ce219299, type: 3}\n",
+            "  m_Name: \n",
+            "  m_EditorClassIdentifier: \n",
+            "  launchedFromSDKPipeline: 0\n",
+            "  completedSDKPipeline: 0\n",
+            "  blueprintId: \n",
+            "  contentType: 0\n",
+            "  assetBundleUnityVersion: \n",
+            "  fallbackStatus: 3\n",
+            ))?,
+            concat!(
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_CorrespondingSourceObject: {fileID: 0}\n",
+            "  m_PrefabInstance: {fileID: 0}\n",
+            "  m_PrefabAsset: {fileID: 0}\n",
+            "  m_GameObject: {fileID: 973945594870973796}\n",
+            "  m_Enabled: 1\n",
+            "  m_EditorHideFlags: 0\n",
+            "  m_Script: {fileID: -1427037861, guid: 4ecd63eff847044b68db9453ce219299, type: 3}\n",
+            "  m_Name: \n",
+            "  m_EditorClassIdentifier: \n",
+            "  launchedFromSDKPipeline: 0\n",
+            "  completedSDKPipeline: 0\n",
+            "  blueprintId: \n",
+            "  contentType: 0\n",
+            "  assetBundleUnityVersion: \n",
+            "  fallbackStatus: 0\n",
+            ),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn prefab() -> anyhow::Result<()> {
+        assert_eq!(
+            filter_yaml(concat!(
+            "PrefabInstance:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 2\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications:\n",
+            "    - target: {fileID: 973945594870973798, guid: 27c023e317f775c45aca5b55f6eab077,\n",
+            "        type: 3}\n",
+            "      propertyPath: fallbackStatus\n",
+            "      value: 3\n",
+            "      objectReference: {fileID: 0}\n",
+            "    m_RemovedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+            ))?,
+            concat!(
+            "PrefabInstance:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 2\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications: []\n",
+            "    m_RemovedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+            ),
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_default_animator_controller {
+    use super::*;
+
+    const YAML: &str = concat!(
+        "MonoBehaviour:\n",
+        "  m_Script: {fileID: 542108242, guid: 67cc4cb7839cd3741b63733d5adf0442, type: 3}\n",
+        "  baseAnimationLayers:\n",
+        "  - isEnabled: 0\n",
+        "    type: 0\n",
+        "    animatorController: {fileID: 0, guid: 00000000000000000000000000000000, type: 0}\n",
+        "    mask: {fileID: 0}\n",
+        "    isDefault: 1\n",
+        "  - isEnabled: 0\n",
+        "    type: 2\n",
+        "    animatorController: {fileID: 9100000, guid: 404d228aeae421f4590305bc4cdaba16, type: 2}\n",
+        "    mask: {fileID: 0}\n",
+        "    isDefault: 0\n",
+    );
+
+    #[test]
+    fn normalize() -> anyhow::Result<()> {
+        let config = FilterConfig {
+            normalize_default_animator_controller: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            filter_yaml_with_config(YAML, &config)?,
+            concat!(
+            "MonoBehaviour:\n",
+            "  m_Script: {fileID: 542108242, guid: 67cc4cb7839cd3741b63733d5adf0442, type: 3}\n",
+            "  baseAnimationLayers:\n",
+            "  - isEnabled: 0\n",
+            "    type: 0\n",
+            "    animatorController: {fileID: 0}\n",
+            "    mask: {fileID: 0}\n",
+            "    isDefault: 1\n",
+            "  - isEnabled: 0\n",
+            "    type: 2\n",
+            "    animatorController: {fileID: 9100000, guid: 404d228aeae421f4590305bc4cdaba16, type: 2}\n",
+            "    mask: {fileID: 0}\n",
+            "    isDefault: 0\n",
+            ),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn disabled_by_default() -> anyhow::Result<()> {
+        assert_eq!(filter_yaml(YAML)?, YAML);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_auto_colliders {
+    use super::*;
+
+    const YAML: &str = concat!(
+        "MonoBehaviour:\n",
+        "  m_Script: {fileID: 542108242, guid: 67cc4cb7839cd3741b63733d5adf0442, type: 3}\n",
+        "  collider_head:\n",
+        "    isMirrored: 0\n",
+        "    state: 0\n",
+        "    transform: {fileID: 400004}\n",
+        "    radius: 0.063420534\n",
+        "    height: 0\n",
+        "    position: {x: 0, y: 0.00000011920929, z: -0.0038499832}\n",
+        "    rotation: {x: 0, y: 0, z: 0, w: 1}\n",
+        "  collider_handL:\n",
+        "    isMirrored: 1\n",
+        "    state: 1\n",
+        "    transform: {fileID: 400006}\n",
+        "    radius: 0.05\n",
+        "    height: 0.1\n",
+        "    position: {x: 0.01, y: 0.02, z: 0}\n",
+        "    rotation: {x: 0.70710677, y: 0, z: 0, w: 0.70710677}\n",
+    );
+
+    #[test]
+    fn normalize() -> anyhow::Result<()> {
+        let config = FilterConfig {
+            normalize_auto_colliders: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            filter_yaml_with_config(YAML, &config)?,
+            concat!(
+            "MonoBehaviour:\n",
+            "  m_Script: {fileID: 542108242, guid: 67cc4cb7839cd3741b63733d5adf0442, type: 3}\n",
+            "  collider_head:\n",
+            "    isMirrored: 0\n",
+            "    state: 0\n",
+            "    transform: {fileID: 400004}\n",
+            "    radius: 0\n",
+            "    height: 0\n",
+            "    position: {x: 0, y: 0, z: 0}\n",
+            "    rotation: {x: 0, y: 0, z: 0, w: 1}\n",
+            "  collider_handL:\n",
+            "    isMirrored: 1\n",
+            "    state: 1\n",
+            "    transform: {fileID: 400006}\n",
+            "    radius: 0.05\n",
+            "    height: 0.1\n",
+            "    position: {x: 0.01, y: 0.02, z: 0}\n",
+            "    rotation: {x: 0.70710677, y: 0, z: 0, w: 0.70710677}\n",
+            ),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn other_script() -> anyhow::Result<()> {
+        let config = FilterConfig {
+            normalize_auto_colliders: true,
+            ..Default::default()
+        };
+        let yaml = YAML.replace("542108242", "11500000");
+        assert_eq!(filter_yaml_with_config(&yaml, &config)?, yaml);
+        Ok(())
+    }
+
+    #[test]
+    fn disabled_by_default() -> anyhow::Result<()> {
+        assert_eq!(filter_yaml(YAML)?, YAML);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_animation_hash_set {
+    use super::*;
+    // see https://github.com/anatawa12/git-vrc/issues/13
+
+    #[test]
+    fn mono_behaviour() -> anyhow::Result<()> {
+        // many fields are omitted
+        assert_eq!(
+            filter_yaml(concat!(
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_CorrespondingSourceObject: {fileID: 0}\n",
+            "  m_PrefabInstance: {fileID: 0}\n",
+            "  m_PrefabAsset: {fileID: 0}\n",
+            "  m_GameObject: {fileID: 973945594870973796}\n",
+            "  m_Enabled: 1\n",
+            "  m_EditorHideFlags: 0\n",
+            "  m_Script: {fileID: 542108242, guid: 67cc4cb7839cd3741b63733d5adf0442, type: 3}\n",
+            "  m_Name:\n",
+            "  m_EditorClassIdentifier: \n",
+            "  AnimationPreset: {fileID: 0}\n",
+            "  animationHashSet:\n",
+            "  - hash: 1127303507\n",
+            "    name: Prone\n",
+            "  - hash: -510014593\n",
+            "    name: Standing\n",
+            "  - hash: 1277114970\n",
+            "    name: Crouching\n",
+            "  - hash: -946967196\n",
+            "    name: Supine\n",
+            "  - hash: -1752920240\n",
+            "    name: Left Side\n",
+            "  - hash: -1868992157\n",
+            "    name: Right Side\n",
+            "  - hash: 1325891751\n",
+            "    name: petan\n",
+            "  - hash: -1840526229\n",
             "    name: SitSeiza\n",
             "  - hash: -2137589102\n",
             "    name: JumpAndFall.Short Fall\n",
@@ -1342,8 +2855,177 @@ mod test_animation_hash_set {
             "      objectReference: {fileID: 0}\n",
             "    - target: {fileID: 973945594870973799, guid: 27c023e317f775c45aca5b55f6eab077,\n",
             "        type: 3}\n",
-            "      propertyPath: animationHashSet.Array.data[38].name\n",
-            "      value: LocalCameraHidden\n",
+            "      propertyPath: animationHashSet.Array.data[38].name\n",
+            "      value: LocalCameraHidden\n",
+            "      objectReference: {fileID: 0}\n",
+            "    m_RemovedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+            ))?,
+            concat!(
+            "PrefabInstance:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 2\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications: []\n",
+            "    m_RemovedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+            ),
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_layer_collision_arr {
+    use super::*;
+    // see https://github.com/anatawa12/git-vrc/issues/12
+
+    #[test]
+    fn mono_behaviour() -> anyhow::Result<()> {
+        // many fields are omitted
+        assert_eq!(
+            filter_yaml(concat!(
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_CorrespondingSourceObject: {fileID: 0}\n",
+            "  m_PrefabInstance: {fileID: 0}\n",
+            "  m_PrefabAsset: {fileID: 0}\n",
+            "  m_GameObject: {fileID: 4306160767114150802}\n",
+            "  m_Enabled: 1\n",
+            "  m_EditorHideFlags: 0\n",
+            "  m_Script: {fileID: -17141911, guid: 661092b4961be7145bfbe56e1e62337b, type: 3}\n",
+            "  m_Name:\n",
+            "  m_EditorClassIdentifier:\n",
+            "  layerCollisionArr: 01010101010001010101010100010001010101010101010101010101010101010101010101000101010101010001000101010101010101010101010101010101010101010100010101010101000100010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101000101010101010001000101010101010101010101010101010101000000010000010100000000000000000000000000000101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010100010101010101000100010101010101010101010101010101010101010101010001010100000100000000000001010101010101010101010101010101010101000101010000010000000000000101010101010101010101010101010101010100010101010101000100010101010101010101010101010101010100000001000001010000000000000000000000000000010101010101010101010101010101000101010000010001010101010000000001010101010101010101000000010000010100000000000100000000000000000101010101010101010101010101010001010100000100010001010101010101010101010101010101010101010101000101010000010001000101010101010101010101010101010101010101010100010101000001000100010101010101010101010101010101010101010101010001010101010100000001010101010101010101010101010101010101010101000101010101010000000101010101010101010101010101010101010101010100010101010101000000010101010101010101010101010101010101010101010001010101010100000001010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101\n",
+            "  capacity: 0\n",
+            "  contentSex: 0\n",
+            ))?,
+            concat!(
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_CorrespondingSourceObject: {fileID: 0}\n",
+            "  m_PrefabInstance: {fileID: 0}\n",
+            "  m_PrefabAsset: {fileID: 0}\n",
+            "  m_GameObject: {fileID: 4306160767114150802}\n",
+            "  m_Enabled: 1\n",
+            "  m_EditorHideFlags: 0\n",
+            "  m_Script: {fileID: -17141911, guid: 661092b4961be7145bfbe56e1e62337b, type: 3}\n",
+            "  m_Name:\n",
+            "  m_EditorClassIdentifier:\n",
+            "  layerCollisionArr: 00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000\n",
+            "  capacity: 0\n",
+            "  contentSex: 0\n",
+            ),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn mono_behaviour_crlf() -> anyhow::Result<()> {
+        let lf = concat!(
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_Script: {fileID: -17141911, guid: 661092b4961be7145bfbe56e1e62337b, type: 3}\n",
+            "  m_Name:\n",
+            "  m_EditorClassIdentifier:\n",
+            "  layerCollisionArr: 0101010101000101010101010001000101010101010101010101010101010101\n",
+            "  capacity: 0\n",
+            "  contentSex: 0\n",
+        );
+        let crlf = lf.replace('\n', "\r\n");
+        let filtered = filter_yaml(&crlf)?;
+        assert_eq!(filtered, filter_yaml(lf)?.replace('\n', "\r\n"));
+        assert_eq!(
+            filtered.matches('\n').count(),
+            filtered.matches("\r\n").count()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn capacity() -> anyhow::Result<()> {
+        assert_eq!(
+            filter_yaml(concat!(
+                "MonoBehaviour:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  m_Script: {fileID: -17141911, guid: 661092b4961be7145bfbe56e1e62337b, type: 3}\n",
+                "  m_Name:\n",
+                "  m_EditorClassIdentifier:\n",
+                "  layerCollisionArr: 0101010101000101010101010001000101010101010101010101010101010101\n",
+                "  capacity: 32\n",
+                "  contentSex: 0\n",
+            ))?
+            .replace(&"0".repeat(32 * 64), "<default>"),
+            concat!(
+                "MonoBehaviour:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  m_Script: {fileID: -17141911, guid: 661092b4961be7145bfbe56e1e62337b, type: 3}\n",
+                "  m_Name:\n",
+                "  m_EditorClassIdentifier:\n",
+                "  layerCollisionArr: <default>\n",
+                "  capacity: 0\n",
+                "  contentSex: 0\n",
+            ),
+        );
+
+        // capacity before layerCollisionArr is also normalized
+        assert_eq!(
+            filter_yaml(concat!(
+                "MonoBehaviour:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  m_Script: {fileID: -17141911, guid: 661092b4961be7145bfbe56e1e62337b, type: 3}\n",
+                "  m_Name:\n",
+                "  capacity: 32\n",
+                "  layerCollisionArr: 0101010101000101010101010001000101010101010101010101010101010101\n",
+            ))?
+            .replace(&"0".repeat(32 * 64), "<default>"),
+            concat!(
+                "MonoBehaviour:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  m_Script: {fileID: -17141911, guid: 661092b4961be7145bfbe56e1e62337b, type: 3}\n",
+                "  m_Name:\n",
+                "  capacity: 0\n",
+                "  layerCollisionArr: <default>\n",
+            ),
+        );
+
+        // capacity without layerCollisionArr is kept
+        let yaml = concat!(
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_Script: {fileID: -17141911, guid: 661092b4961be7145bfbe56e1e62337b, type: 3}\n",
+            "  m_Name:\n",
+            "  capacity: 32\n",
+        );
+        assert_eq!(filter_yaml(yaml)?, yaml);
+
+        // capacity of other scripts are kept
+        let yaml = concat!(
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_Script: {fileID: 11500000, guid: 22203902d63dec94194fefc3e155c43b, type: 3}\n",
+            "  m_Name:\n",
+            "  capacity: 32\n",
+        );
+        assert_eq!(filter_yaml(yaml)?, yaml);
+        Ok(())
+    }
+
+    #[test]
+    fn prefab() -> anyhow::Result<()> {
+        assert_eq!(
+            filter_yaml(concat!(
+            "PrefabInstance:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 2\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications:\n",
+            "    - target: {fileID: 973945594870973799, guid: 27c023e317f775c45aca5b55f6eab077,\n",
+            "        type: 3}\n",
+            "      propertyPath: layerCollisionArr\n",
+            "      value: 00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000 \n",
             "      objectReference: {fileID: 0}\n",
             "    m_RemovedComponents: []\n",
             "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
@@ -1364,13 +3046,12 @@ mod test_animation_hash_set {
 }
 
 #[cfg(test)]
-mod test_layer_collision_arr {
+mod test_completed_sdk_pipeline {
     use super::*;
-    // see https://github.com/anatawa12/git-vrc/issues/12
+    // see https://github.com/anatawa12/git-vrc/issues/17
 
     #[test]
     fn mono_behaviour() -> anyhow::Result<()> {
-        // many fields are omitted
         assert_eq!(
             filter_yaml(concat!(
             "MonoBehaviour:\n",
@@ -1378,15 +3059,18 @@ mod test_layer_collision_arr {
             "  m_CorrespondingSourceObject: {fileID: 0}\n",
             "  m_PrefabInstance: {fileID: 0}\n",
             "  m_PrefabAsset: {fileID: 0}\n",
-            "  m_GameObject: {fileID: 4306160767114150802}\n",
+            "  m_GameObject: {fileID: 973945594870973796}\n",
             "  m_Enabled: 1\n",
             "  m_EditorHideFlags: 0\n",
-            "  m_Script: {fileID: -17141911, guid: 661092b4961be7145bfbe56e1e62337b, type: 3}\n",
-            "  m_Name:\n",
-            "  m_EditorClassIdentifier:\n",
-            "  layerCollisionArr: 01010101010001010101010100010001010101010101010101010101010101010101010101000101010101010001000101010101010101010101010101010101010101010100010101010101000100010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101000101010101010001000101010101010101010101010101010101000000010000010100000000000000000000000000000101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010100010101010101000100010101010101010101010101010101010101010101010001010100000100000000000001010101010101010101010101010101010101000101010000010000000000000101010101010101010101010101010101010100010101010101000100010101010101010101010101010101010100000001000001010000000000000000000000000000010101010101010101010101010101000101010000010001010101010000000001010101010101010101000000010000010100000000000100000000000000000101010101010101010101010101010001010100000100010001010101010101010101010101010101010101010101000101010000010001000101010101010101010101010101010101010101010100010101000001000100010101010101010101010101010101010101010101010001010101010100000001010101010101010101010101010101010101010101000101010101010000000101010101010101010101010101010101010101010100010101010101000000010101010101010101010101010101010101010101010001010101010100000001010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101010101\n",
-            "  capacity: 0\n",
-            "  contentSex: 0\n",
+            "  m_Script: {fileID: -1427037861, guid: 4ecd63eff847044b68db9453ce219299, type: 3}\n",
+            "  m_Name: \n",
+            "  m_EditorClassIdentifier: \n",
+            "  launchedFromSDKPipeline: 0\n",
+            "  completedSDKPipeline: 1\n",
+            "  blueprintId: \n",
+            "  contentType: 0\n",
+            "  assetBundleUnityVersion: \n",
+            "  fallbackStatus: 0\n",
             ))?,
             concat!(
             "MonoBehaviour:\n",
@@ -1394,107 +3078,670 @@ mod test_layer_collision_arr {
             "  m_CorrespondingSourceObject: {fileID: 0}\n",
             "  m_PrefabInstance: {fileID: 0}\n",
             "  m_PrefabAsset: {fileID: 0}\n",
-            "  m_GameObject: {fileID: 4306160767114150802}\n",
+            "  m_GameObject: {fileID: 973945594870973796}\n",
             "  m_Enabled: 1\n",
             "  m_EditorHideFlags: 0\n",
-            "  m_Script: {fileID: -17141911, guid: 661092b4961be7145bfbe56e1e62337b, type: 3}\n",
-            "  m_Name:\n",
+            "  m_Script: {fileID: -1427037861, guid: 4ecd63eff847044b68db9453ce219299, type: 3}\n",
+            "  m_Name: \n",
+            "  m_EditorClassIdentifier: \n",
+            "  launchedFromSDKPipeline: 0\n",
+            "  completedSDKPipeline: 0\n",
+            "  blueprintId: \n",
+            "  contentType: 0\n",
+            "  assetBundleUnityVersion: \n",
+            "  fallbackStatus: 0\n",
+            ),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn prefab() -> anyhow::Result<()> {
+        assert_eq!(
+            filter_yaml(concat!(
+            "PrefabInstance:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 2\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications:\n",
+            "    - target: {fileID: 973945594870973798, guid: 27c023e317f775c45aca5b55f6eab077,\n",
+            "        type: 3}\n",
+            "      propertyPath: completedSDKPipeline\n",
+            "      value: 1\n",
+            "      objectReference: {fileID: 0}\n",
+            "    m_RemovedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+            ))?,
+            concat!(
+            "PrefabInstance:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 2\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications: []\n",
+            "    m_RemovedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+            ),
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_editor_class_identifier {
+    use super::*;
+
+    #[test]
+    fn normalize() -> anyhow::Result<()> {
+        let config = FilterConfig {
+            normalize_editor_class_identifier: true,
+            ..Default::default()
+        };
+        let expected = concat!(
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_Script: {fileID: 11500000, guid: 22203902d63dec94194fefc3e155c43b, type: 3}\n",
+            "  m_Name: \n",
             "  m_EditorClassIdentifier:\n",
-            "  layerCollisionArr: 00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000\n",
-            "  capacity: 0\n",
-            "  contentSex: 0\n",
+            "  udonAssembly:\n",
+        );
+        for editor_class_identifier in ["", " ", "  ", " ''", " \"\""] {
+            let yaml = format!(
+                concat!(
+                    "MonoBehaviour:\n",
+                    "  m_ObjectHideFlags: 0\n",
+                    "  m_Script: {{fileID: 11500000, guid: 22203902d63dec94194fefc3e155c43b, type: 3}}\n",
+                    "  m_Name: \n",
+                    "  m_EditorClassIdentifier:{}\n",
+                    "  udonAssembly:\n",
+                ),
+                editor_class_identifier
+            );
+            assert_eq!(filter_yaml_with_config(&yaml, &config)?, expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn keep_non_empty() -> anyhow::Result<()> {
+        let config = FilterConfig {
+            normalize_editor_class_identifier: true,
+            ..Default::default()
+        };
+        let yaml = concat!(
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_EditorClassIdentifier: Assembly::Class \n",
+            "  udonAssembly:\n",
+        );
+        assert_eq!(filter_yaml_with_config(yaml, &config)?, yaml);
+        Ok(())
+    }
+
+    #[test]
+    fn disabled_by_default() -> anyhow::Result<()> {
+        let yaml = concat!(
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_EditorClassIdentifier: \n",
+            "  udonAssembly:\n",
+        );
+        assert_eq!(filter_yaml(yaml)?, yaml);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_view_position {
+    use super::*;
+
+    fn avatar_descriptor(view_position: &str) -> String {
+        format!(
+            concat!(
+                "MonoBehaviour:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  m_GameObject: {{fileID: 1817598470}}\n",
+                "  m_Enabled: 1\n",
+                "  m_Script: {{fileID: 542108242, guid: 67cc4cb7839cd3741b63733d5adf0442, type: 3}}\n",
+                "  m_Name: \n",
+                "  m_EditorClassIdentifier: \n",
+                "  Name: \n",
+                "  ViewPosition: {}\n",
+                "  Animations: 0\n",
+            ),
+            view_position
+        )
+    }
+
+    #[test]
+    fn recomputed() -> anyhow::Result<()> {
+        let config = FilterConfig {
+            normalize_view_position: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            filter_yaml_with_config(
+                &avatar_descriptor("{x: 0.0000001, y: 1.6000001, z: 0.19999999}"),
+                &config
+            )?,
+            avatar_descriptor("{x: 0, y: 1.6, z: 0.2}"),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn user_set() -> anyhow::Result<()> {
+        let config = FilterConfig {
+            normalize_view_position: true,
+            ..Default::default()
+        };
+        let yaml = avatar_descriptor("{x: 0, y: 1.4237, z: 0.0832}");
+        assert_eq!(filter_yaml_with_config(&yaml, &config)?, yaml);
+        Ok(())
+    }
+
+    #[test]
+    fn disabled_by_default() -> anyhow::Result<()> {
+        let yaml = avatar_descriptor("{x: 0.0000001, y: 1.6000001, z: 0.19999999}");
+        assert_eq!(filter_yaml(&yaml)?, yaml);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_serialized_version {
+    use super::*;
+
+    #[test]
+    fn mono_behaviour() -> anyhow::Result<()> {
+        assert_eq!(
+            filter_yaml(concat!(
+                "MonoBehaviour:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  serializedVersion: 3\n",
+                "  m_GameObject: {fileID: 973945594870973796}\n",
+                "  m_Enabled: 1\n",
+                "  completedSDKPipeline: 1\n",
+                "  fallbackStatus: 3\n",
+            ))?,
+            concat!(
+                "MonoBehaviour:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  serializedVersion: 3\n",
+                "  m_GameObject: {fileID: 973945594870973796}\n",
+                "  m_Enabled: 1\n",
+                "  completedSDKPipeline: 0\n",
+                "  fallbackStatus: 0\n",
+            ),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn prefab() -> anyhow::Result<()> {
+        assert_eq!(
+            filter_yaml(concat!(
+            "PrefabInstance:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 3\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications:\n",
+            "    - target: {fileID: 973945594870973798, guid: 27c023e317f775c45aca5b55f6eab077,\n",
+            "        type: 3}\n",
+            "      propertyPath: fallbackStatus\n",
+            "      value: 3\n",
+            "      objectReference: {fileID: 0}\n",
+            "    m_RemovedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+            ))?,
+            concat!(
+            "PrefabInstance:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 3\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications: []\n",
+            "    m_RemovedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+            ),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn prefab_unity_2022() -> anyhow::Result<()> {
+        assert_eq!(
+            filter_yaml(concat!(
+            "PrefabInstance:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 3\n",
+            "  m_Modification:\n",
+            "    serializedVersion: 3\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications:\n",
+            "    - target: {fileID: 973945594870973798, guid: 27c023e317f775c45aca5b55f6eab077,\n",
+            "        type: 3}\n",
+            "      propertyPath: m_Name\n",
+            "      value: Avatar\n",
+            "      objectReference: {fileID: 0}\n",
+            "    - target: {fileID: 973945594870973798, guid: 27c023e317f775c45aca5b55f6eab077,\n",
+            "        type: 3}\n",
+            "      propertyPath: fallbackStatus\n",
+            "      value: 3\n",
+            "      objectReference: {fileID: 0}\n",
+            "    m_RemovedComponents: []\n",
+            "    m_RemovedGameObjects: []\n",
+            "    m_AddedGameObjects: []\n",
+            "    m_AddedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+            ))?,
+            concat!(
+            "PrefabInstance:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 3\n",
+            "  m_Modification:\n",
+            "    serializedVersion: 3\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications:\n",
+            "    - target: {fileID: 973945594870973798, guid: 27c023e317f775c45aca5b55f6eab077,\n",
+            "        type: 3}\n",
+            "      propertyPath: m_Name\n",
+            "      value: Avatar\n",
+            "      objectReference: {fileID: 0}\n",
+            "    m_RemovedComponents: []\n",
+            "    m_RemovedGameObjects: []\n",
+            "    m_AddedGameObjects: []\n",
+            "    m_AddedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
             ),
         );
         Ok(())
     }
 
     #[test]
-    fn prefab() -> anyhow::Result<()> {
+    fn prefab_without_serialized_version() -> anyhow::Result<()> {
         assert_eq!(
             filter_yaml(concat!(
             "PrefabInstance:\n",
             "  m_ObjectHideFlags: 0\n",
-            "  serializedVersion: 2\n",
             "  m_Modification:\n",
             "    m_TransformParent: {fileID: 0}\n",
             "    m_Modifications:\n",
-            "    - target: {fileID: 973945594870973799, guid: 27c023e317f775c45aca5b55f6eab077,\n",
+            "    - target: {fileID: 9122363655180540528, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
             "        type: 3}\n",
-            "      propertyPath: layerCollisionArr\n",
-            "      value: 00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000 \n",
+            "      propertyPath: fallbackStatus\n",
+            "      value: 3\n",
             "      objectReference: {fileID: 0}\n",
             "    m_RemovedComponents: []\n",
-            "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
             ))?,
             concat!(
             "PrefabInstance:\n",
             "  m_ObjectHideFlags: 0\n",
-            "  serializedVersion: 2\n",
             "  m_Modification:\n",
             "    m_TransformParent: {fileID: 0}\n",
             "    m_Modifications: []\n",
             "    m_RemovedComponents: []\n",
-            "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn prefab_serialized_version_last() -> anyhow::Result<()> {
+        assert_eq!(
+            filter_yaml(concat!(
+            "PrefabInstance:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications:\n",
+            "    - target: {fileID: 9122363655180540528, guid: 26db88bf250934ccca835bd9318c0eeb,\n",
+            "        type: 3}\n",
+            "      propertyPath: fallbackStatus\n",
+            "      value: 3\n",
+            "      objectReference: {fileID: 0}\n",
+            "    m_RemovedComponents: []\n",
+            "  serializedVersion: 2\n",
+            ))?,
+            concat!(
+                "PrefabInstance:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  m_Modification:\n",
+                "    m_TransformParent: {fileID: 0}\n",
+                "    m_Modifications: []\n",
+                "    m_RemovedComponents: []\n",
+                "  serializedVersion: 2\n",
+            )
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_negative_zero {
+    use super::*;
+
+    #[test]
+    fn transform() -> anyhow::Result<()> {
+        let config = FilterConfig {
+            normalize_negative_zero: true,
+            ..Default::default()
+        };
+        let yaml = concat!(
+            "Transform:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_GameObject: {fileID: 1}\n",
+            "  m_LocalRotation: {x: -0, y: -0, z: -0, w: 1}\n",
+            "  m_LocalPosition: {x: -0, y: -0.5, z: -0}\n",
+            "  m_LocalScale: {x: 1, y: -0, z: -10}\n",
+            "  m_Children: []\n",
+            "  m_Father: {fileID: 0}\n",
+        );
+        assert_eq!(
+            filter_yaml_with_config(yaml, &config)?,
+            concat!(
+                "Transform:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  m_GameObject: {fileID: 1}\n",
+                "  m_LocalRotation: {x: -0, y: -0, z: -0, w: 1}\n",
+                "  m_LocalPosition: {x: 0, y: -0.5, z: 0}\n",
+                "  m_LocalScale: {x: 1, y: 0, z: -10}\n",
+                "  m_Children: []\n",
+                "  m_Father: {fileID: 0}\n",
             ),
         );
+
+        // disabled by default
+        assert_eq!(filter_yaml(yaml)?, yaml);
         Ok(())
     }
 }
 
 #[cfg(test)]
-mod test_completed_sdk_pipeline {
+mod test_log_replaced_value {
     use super::*;
-    // see https://github.com/anatawa12/git-vrc/issues/17
+
+    #[test]
+    fn fallback_status() {
+        assert!(log_replaced_value("fallbackStatus", " 3", "0"));
+        assert!(!log_replaced_value("fallbackStatus", " 0", "0"));
+    }
+}
+
+#[cfg(test)]
+mod test_trim_name {
+    use super::*;
+
+    #[test]
+    fn game_object() -> anyhow::Result<()> {
+        let config = FilterConfig {
+            trim_name: true,
+            ..Default::default()
+        };
+        let yaml = concat!(
+            "GameObject:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_Name: 'My Object  '\n",
+            "  m_TagString: 'Untagged '\n",
+        );
+        assert_eq!(
+            filter_yaml_with_config(yaml, &config)?,
+            concat!(
+                "GameObject:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  m_Name: 'My Object'\n",
+                "  m_TagString: 'Untagged '\n",
+            ),
+        );
+
+        // disabled by default
+        assert_eq!(filter_yaml(yaml)?, yaml);
+        Ok(())
+    }
 
     #[test]
     fn mono_behaviour() -> anyhow::Result<()> {
+        let config = FilterConfig {
+            trim_name: true,
+            ..Default::default()
+        };
         assert_eq!(
-            filter_yaml(concat!(
-            "MonoBehaviour:\n",
-            "  m_ObjectHideFlags: 0\n",
-            "  m_CorrespondingSourceObject: {fileID: 0}\n",
-            "  m_PrefabInstance: {fileID: 0}\n",
-            "  m_PrefabAsset: {fileID: 0}\n",
-            "  m_GameObject: {fileID: 973945594870973796}\n",
-            "  m_Enabled: 1\n",
-            "  m_EditorHideFlags: 0\n",
-            "  m_Script: {fileID: -1427037861, guid: 4ecd63eff847044b68db9453ce219299, type: 3}\n",
-            "  m_Name: \n",
-            "  m_EditorClassIdentifier: \n",
-            "  launchedFromSDKPipeline: 0\n",
-            "  completedSDKPipeline: 1\n",
-            "  blueprintId: \n",
-            "  contentType: 0\n",
-            "  assetBundleUnityVersion: \n",
-            "  fallbackStatus: 0\n",
-            ))?,
+            filter_yaml_with_config(
+                concat!(
+                    "MonoBehaviour:\n",
+                    "  m_ObjectHideFlags: 0\n",
+                    "  m_Name: \" Behaviour \"\n",
+                    "  m_EditorClassIdentifier:\n",
+                ),
+                &config
+            )?,
             concat!(
-            "MonoBehaviour:\n",
-            "  m_ObjectHideFlags: 0\n",
-            "  m_CorrespondingSourceObject: {fileID: 0}\n",
-            "  m_PrefabInstance: {fileID: 0}\n",
-            "  m_PrefabAsset: {fileID: 0}\n",
-            "  m_GameObject: {fileID: 973945594870973796}\n",
-            "  m_Enabled: 1\n",
-            "  m_EditorHideFlags: 0\n",
-            "  m_Script: {fileID: -1427037861, guid: 4ecd63eff847044b68db9453ce219299, type: 3}\n",
-            "  m_Name: \n",
-            "  m_EditorClassIdentifier: \n",
-            "  launchedFromSDKPipeline: 0\n",
-            "  completedSDKPipeline: 0\n",
-            "  blueprintId: \n",
-            "  contentType: 0\n",
-            "  assetBundleUnityVersion: \n",
-            "  fallbackStatus: 0\n",
+                "MonoBehaviour:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  m_Name: \" Behaviour\"\n",
+                "  m_EditorClassIdentifier:\n",
             ),
         );
         Ok(())
     }
 
+    #[test]
+    fn kept() -> anyhow::Result<()> {
+        let config = FilterConfig {
+            trim_name: true,
+            ..Default::default()
+        };
+        for name in ["Object", "'Object'", "'  Object'", "\"Object\"", "''"] {
+            let yaml = format!("GameObject:\n  m_ObjectHideFlags: 0\n  m_Name: {}\n", name);
+            assert_eq!(filter_yaml_with_config(&yaml, &config)?, yaml);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_issues {
+    use super::*;
+
+    /// a documented issue and the values the rule for the issue normalizes.
+    /// every rule for an issue must have a row in [`cases`].
+    struct Case {
+        issue: u32,
+        /// fields of MonoBehaviour before the clean
+        fields: String,
+        /// fields of MonoBehaviour after the clean. None if the whole object is removed.
+        cleaned: Option<String>,
+        /// propertyPath and value of prefab modification which should be omitted.
+        /// None if the issue is not about values overridable in prefab.
+        modification: Option<(&'static str, &'static str)>,
+    }
+
+    fn cases() -> Vec<Case> {
+        fn case(
+            issue: u32,
+            fields: &str,
+            cleaned: Option<&str>,
+            modification: Option<(&'static str, &'static str)>,
+        ) -> Case {
+            Case {
+                issue,
+                fields: fields.to_owned(),
+                cleaned: cleaned.map(ToOwned::to_owned),
+                modification,
+            }
+        }
+
+        vec![
+            case(
+                3,
+                "  m_Script: {fileID: 229740497, guid: 4ecd63eff847044b68db9453ce219299, type: 3}\n",
+                None,
+                None,
+            ),
+            case(
+                5,
+                "  DynamicMaterials:\n  - {fileID: 2100000, guid: 3f13a5d1eb038764b804d1aabffed55f, type: 2}\n",
+                Some("  DynamicMaterials: []\n"),
+                Some(("DynamicMaterials.Array.size", "1")),
+            ),
+            case(
+                5,
+                "  DynamicPrefabs:\n  - {fileID: 2100000, guid: 3f13a5d1eb038764b804d1aabffed55f, type: 2}\n",
+                Some("  DynamicPrefabs: []\n"),
+                Some(("DynamicPrefabs.Array.data[0]", "")),
+            ),
+            Case {
+                issue: 12,
+                fields: format!("  layerCollisionArr: {}\n", "01".repeat(1024)),
+                cleaned: Some(format!("  layerCollisionArr: {}\n", "0".repeat(2048))),
+                modification: Some(("layerCollisionArr", "0101")),
+            },
+            case(
+                13,
+                "  animationHashSet:\n  - hash: 1127303507\n    name: Prone\n",
+                Some("  animationHashSet: []\n"),
+                Some(("animationHashSet.Array.data[0].hash", "1127303507")),
+            ),
+            case(
+                14,
+                "  fallbackStatus: 3\n",
+                Some("  fallbackStatus: 0\n"),
+                Some(("fallbackStatus", "3")),
+            ),
+            case(
+                17,
+                "  completedSDKPipeline: 1\n",
+                Some("  completedSDKPipeline: 0\n"),
+                Some(("completedSDKPipeline", "1")),
+            ),
+            case(
+                19,
+                concat!(
+                    "  baseAnimationLayers:\n",
+                    "  - isEnabled: 0\n",
+                    "    mask: {fileID: 31900000, guid: b2b8bad9583e56a46a3e21795e96ad92, type: 2}\n",
+                ),
+                Some(concat!(
+                    "  baseAnimationLayers:\n",
+                    "  - isEnabled: 0\n",
+                    "    mask: {fileID: 0}\n",
+                )),
+                Some(("baseAnimationLayers.Array.data[0].mask", "")),
+            ),
+            case(
+                20,
+                "  foldout_gizmos: 1\n",
+                Some("  foldout_gizmos: 0\n"),
+                Some(("foldout_gizmos", "1")),
+            ),
+        ]
+    }
+
+    #[test]
+    fn mono_behaviour() -> anyhow::Result<()> {
+        for case in cases() {
+            let yaml = format!("MonoBehaviour:\n{}", case.fields);
+            let expected = match &case.cleaned {
+                Some(cleaned) => format!("MonoBehaviour:\n{}", cleaned),
+                None => String::new(),
+            };
+            assert_eq!(filter_yaml(&yaml)?, expected, "issue #{}", case.issue);
+        }
+        Ok(())
+    }
+
     #[test]
     fn prefab() -> anyhow::Result<()> {
+        for case in cases() {
+            let (property_path, value) = match case.modification {
+                Some(modification) => modification,
+                None => continue,
+            };
+            let yaml = format!(
+                concat!(
+                    "PrefabInstance:\n",
+                    "  m_ObjectHideFlags: 0\n",
+                    "  serializedVersion: 2\n",
+                    "  m_Modification:\n",
+                    "    m_TransformParent: {{fileID: 0}}\n",
+                    "    m_Modifications:\n",
+                    "    - target: {{fileID: 973945594870973798, guid: 27c023e317f775c45aca5b55f6eab077,\n",
+                    "        type: 3}}\n",
+                    "      propertyPath: {}\n",
+                    "      value: {}\n",
+                    "      objectReference: {{fileID: 0}}\n",
+                    "    m_RemovedComponents: []\n",
+                    "  m_SourcePrefab: {{fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}}\n",
+                ),
+                property_path, value,
+            );
+            assert_eq!(
+                filter_yaml(&yaml)?,
+                concat!(
+                    "PrefabInstance:\n",
+                    "  m_ObjectHideFlags: 0\n",
+                    "  serializedVersion: 2\n",
+                    "  m_Modification:\n",
+                    "    m_TransformParent: {fileID: 0}\n",
+                    "    m_Modifications: []\n",
+                    "    m_RemovedComponents: []\n",
+                    "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+                ),
+                "issue #{}",
+                case.issue,
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_orphan_array_size {
+    use super::*;
+
+    const YAML: &str = concat!(
+        "PrefabInstance:\n",
+        "  m_ObjectHideFlags: 0\n",
+        "  serializedVersion: 2\n",
+        "  m_Modification:\n",
+        "    m_TransformParent: {fileID: 0}\n",
+        "    m_Modifications:\n",
+        "    - target: {fileID: 973945594870973798, guid: 27c023e317f775c45aca5b55f6eab077,\n",
+        "        type: 3}\n",
+        "      propertyPath: materials.Array.size\n",
+        "      value: 2\n",
+        "      objectReference: {fileID: 0}\n",
+        "    - target: {fileID: 973945594870973798, guid: 27c023e317f775c45aca5b55f6eab077,\n",
+        "        type: 3}\n",
+        "      propertyPath: targets.Array.size\n",
+        "      value: 1\n",
+        "      objectReference: {fileID: 0}\n",
+        "    - target: {fileID: 973945594870973798, guid: 27c023e317f775c45aca5b55f6eab077,\n",
+        "        type: 3}\n",
+        "      propertyPath: targets.Array.data[0]\n",
+        "      value: \n",
+        "      objectReference: {fileID: 400000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+        "    - target: {fileID: 1234, guid: 27c023e317f775c45aca5b55f6eab077, type: 3}\n",
+        "      propertyPath: targets.Array.size\n",
+        "      value: 1\n",
+        "      objectReference: {fileID: 0}\n",
+        "    m_RemovedComponents: []\n",
+        "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+    );
+
+    #[test]
+    fn remove() -> anyhow::Result<()> {
+        let config = FilterConfig {
+            remove_orphan_array_size: true,
+            ..Default::default()
+        };
         assert_eq!(
-            filter_yaml(concat!(
+            filter_yaml_with_config(YAML, &config)?,
+            concat!(
             "PrefabInstance:\n",
             "  m_ObjectHideFlags: 0\n",
             "  serializedVersion: 2\n",
@@ -1503,23 +3750,354 @@ mod test_completed_sdk_pipeline {
             "    m_Modifications:\n",
             "    - target: {fileID: 973945594870973798, guid: 27c023e317f775c45aca5b55f6eab077,\n",
             "        type: 3}\n",
-            "      propertyPath: completedSDKPipeline\n",
+            "      propertyPath: targets.Array.size\n",
             "      value: 1\n",
             "      objectReference: {fileID: 0}\n",
+            "    - target: {fileID: 973945594870973798, guid: 27c023e317f775c45aca5b55f6eab077,\n",
+            "        type: 3}\n",
+            "      propertyPath: targets.Array.data[0]\n",
+            "      value: \n",
+            "      objectReference: {fileID: 400000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
             "    m_RemovedComponents: []\n",
             "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
-            ))?,
+            ),
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn disabled_by_default() -> anyhow::Result<()> {
+        assert_eq!(filter_yaml(YAML)?, YAML);
+        Ok(())
+    }
+
+    #[test]
+    fn is_orphan_array_size_test() {
+        let target =
+            || ObjectReference::new(1234, "27c023e317f775c45aca5b55f6eab077".to_owned(), 3);
+        let mut arrays = ModifiedArrays::new();
+        arrays.insert((target(), "targets.Array".to_owned()));
+        assert!(!is_orphan_array_size(
+            target(),
+            "targets.Array.size",
+            &arrays
+        ));
+        assert!(is_orphan_array_size(
+            target(),
+            "materials.Array.size",
+            &arrays
+        ));
+        assert!(!is_orphan_array_size(target(), "size", &arrays));
+        assert!(!is_orphan_array_size(target(), "m_Size.size", &arrays));
+    }
+}
+
+#[cfg(test)]
+mod test_scalar_decoding {
+    use super::*;
+
+    // rules compare decoded values so quoting or escaping must not change the result
+    fn modification(property_path: &str, value: &str) -> String {
+        format!(
             concat!(
-            "PrefabInstance:\n",
-            "  m_ObjectHideFlags: 0\n",
-            "  serializedVersion: 2\n",
-            "  m_Modification:\n",
-            "    m_TransformParent: {fileID: 0}\n",
-            "    m_Modifications: []\n",
-            "    m_RemovedComponents: []\n",
-            "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+                "PrefabInstance:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  serializedVersion: 2\n",
+                "  m_Modification:\n",
+                "    m_TransformParent: {{fileID: 0}}\n",
+                "    m_Modifications:\n",
+                "    - target: {{fileID: 973945594870973798, guid: 27c023e317f775c45aca5b55f6eab077,\n",
+                "        type: 3}}\n",
+                "      propertyPath: {}\n",
+                "      value: {}\n",
+                "      objectReference: {{fileID: 0}}\n",
+                "    m_RemovedComponents: []\n",
+                "  m_SourcePrefab: {{fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}}\n",
+            ),
+            property_path, value,
+        )
+    }
+
+    const OMITTED: &str = concat!(
+        "PrefabInstance:\n",
+        "  m_ObjectHideFlags: 0\n",
+        "  serializedVersion: 2\n",
+        "  m_Modification:\n",
+        "    m_TransformParent: {fileID: 0}\n",
+        "    m_Modifications: []\n",
+        "    m_RemovedComponents: []\n",
+        "  m_SourcePrefab: {fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}\n",
+    );
+
+    #[test]
+    fn quoted_value() -> anyhow::Result<()> {
+        for value in ["3", "'3'", "\"3\""] {
+            let yaml = modification("fallbackStatus", value);
+            assert_eq!(filter_yaml(&yaml)?, OMITTED, "{}", value);
+        }
+        for value in ["", "''", "\"\""] {
+            let yaml = modification("serializedProgramAsset", value);
+            assert_eq!(filter_yaml(&yaml)?, OMITTED, "{:?}", value);
+        }
+        // non-empty quoted value is not an empty value
+        let yaml = modification("serializedProgramAsset", "' '");
+        assert_eq!(filter_yaml(&yaml)?, yaml);
+        Ok(())
+    }
+
+    #[test]
+    fn quoted_property_path() -> anyhow::Result<()> {
+        for property_path in [
+            "'completedSDKPipeline'",
+            "\"completedSDKPipeline\"",
+            "\"completed\\x53DKPipeline\"",
+            "'DynamicMaterials.Array.data[0]'",
+        ] {
+            let yaml = modification(property_path, "1");
+            assert_eq!(filter_yaml(&yaml)?, OMITTED, "{}", property_path);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn null_value() -> anyhow::Result<()> {
+        // `value:`, `value: ` and `value: ~` are null and treated same as empty value
+        for value in ["value:", "value: ", "value: ~"] {
+            let yaml = modification("serializedProgramAsset", "").replace("value: ", value);
+            assert_eq!(filter_yaml(&yaml)?, OMITTED, "{:?}", value);
+        }
+        // quoted `~` is a string, not null
+        let yaml = modification("serializedProgramAsset", "'~'");
+        assert_eq!(filter_yaml(&yaml)?, yaml);
+        let yaml = modification("serializedProgramAsset", "\"~\"");
+        assert_eq!(filter_yaml(&yaml)?, yaml);
+        Ok(())
+    }
+
+    #[test]
+    fn block_scalar_value() -> anyhow::Result<()> {
+        // block scalar is decoded like other scalars
+        for value in ["|-\n        3", ">-\n        3"] {
+            let yaml = modification("fallbackStatus", value);
+            assert_eq!(filter_yaml(&yaml)?, OMITTED, "{:?}", value);
+        }
+        let yaml = modification("m_Name", "|\n        first line\n\n        second line\n");
+        assert_eq!(filter_yaml(&yaml)?, yaml);
+
+        // modifications around block scalar are kept or omitted without corruption
+        let modifications = |omitted: bool| {
+            format!(
+                concat!(
+                    "PrefabInstance:\n",
+                    "  m_ObjectHideFlags: 0\n",
+                    "  serializedVersion: 2\n",
+                    "  m_Modification:\n",
+                    "    m_TransformParent: {{fileID: 0}}\n",
+                    "    m_Modifications:\n",
+                    "    - target: {{fileID: 1, guid: 27c023e317f775c45aca5b55f6eab077, type: 3}}\n",
+                    "      propertyPath: m_Name\n",
+                    "      value: |\n",
+                    "        first line\n",
+                    "        second line\n",
+                    "      objectReference: {{fileID: 0}}\n",
+                    "{}",
+                    "    - target: {{fileID: 1, guid: 27c023e317f775c45aca5b55f6eab077, type: 3}}\n",
+                    "      propertyPath: m_Text\n",
+                    "      value: >-\n",
+                    "        folded\n",
+                    "        text\n",
+                    "      objectReference: {{fileID: 0}}\n",
+                    "    m_RemovedComponents: []\n",
+                    "  m_SourcePrefab: {{fileID: 100100000, guid: 8894fa7e4588a5c4fab98453e558847d, type: 3}}\n",
+                ),
+                if omitted {
+                    ""
+                } else {
+                    concat!(
+                        "    - target: {fileID: 1, guid: 27c023e317f775c45aca5b55f6eab077, type: 3}\n",
+                        "      propertyPath: fallbackStatus\n",
+                        "      value: |-\n",
+                        "        3\n",
+                        "      objectReference: {fileID: 0}\n",
+                    )
+                },
+            )
+        };
+        assert_eq!(filter_yaml(&modifications(false))?, modifications(true));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_station_null_references {
+    use super::*;
+
+    fn station(script: &str, enter: &str, exit: &str) -> String {
+        format!(
+            concat!(
+                "MonoBehaviour:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  m_GameObject: {{fileID: 1}}\n",
+                "  m_Enabled: 1\n",
+                "  m_Script: {}\n",
+                "  m_Name: \n",
+                "  PlayerMobility: 0\n",
+                "  canUseStationFromStation: 1\n",
+                "  animatorController: {{fileID: 0, guid: 0123456789abcdef0123456789abcdef, type: 2}}\n",
+                "  disableStationExit: 0\n",
+                "  seated: 1\n",
+                "  stationEnterPlayerLocation: {}\n",
+                "  stationExitPlayerLocation: {}\n",
             ),
+            script, enter, exit,
+        )
+    }
+
+    const STATION: &str = "{fileID: 1073094524, guid: 661092b4961be7145bfbe56e1e62337b, type: 3}";
+    const OTHER: &str = "{fileID: 11500000, guid: 0123456789abcdef0123456789abcdef, type: 3}";
+
+    #[test]
+    fn station_null_references() -> anyhow::Result<()> {
+        let config = FilterConfig {
+            normalize_station_null_references: true,
+            ..Default::default()
+        };
+        let null = "{fileID: 0, guid: 0000000000000000e000000000000000, type: 0}";
+        let assigned = "{fileID: 5}";
+        let yaml = station(STATION, null, assigned);
+        assert_eq!(
+            filter_yaml_with_config(&yaml, &config)?,
+            station(STATION, "{fileID: 0}", assigned).replace(
+                "{fileID: 0, guid: 0123456789abcdef0123456789abcdef, type: 2}",
+                "{fileID: 0}"
+            ),
+        );
+        // kept if the rule is disabled
+        assert_eq!(filter_yaml(&yaml)?, yaml);
+        // kept for other scripts
+        let yaml = station(OTHER, null, assigned);
+        assert_eq!(filter_yaml_with_config(&yaml, &config)?, yaml);
+        Ok(())
+    }
+
+    #[test]
+    fn avatar_pedestal_null_placement() -> anyhow::Result<()> {
+        let config = FilterConfig {
+            normalize_station_null_references: true,
+            ..Default::default()
+        };
+        let pedestal = |placement: &str| {
+            format!(
+                concat!(
+                    "MonoBehaviour:\n",
+                    "  m_ObjectHideFlags: 0\n",
+                    "  m_Script: {{fileID: 1619588052, guid: 661092b4961be7145bfbe56e1e62337b, type: 3}}\n",
+                    "  blueprintId: avtr_00000000-0000-0000-0000-000000000000\n",
+                    "  Placement: {}\n",
+                    "  ChangeAvatarsOnUse: 0\n",
+                    "  scale: 1\n",
+                ),
+                placement,
+            )
+        };
+        assert_eq!(
+            filter_yaml_with_config(
+                &pedestal("{fileID: 0, guid: 0000000000000000e000000000000000, type: 0}"),
+                &config
+            )?,
+            pedestal("{fileID: 0}"),
         );
+        let yaml = pedestal("{fileID: 400000, guid: 0123456789abcdef0123456789abcdef, type: 3}");
+        assert_eq!(filter_yaml_with_config(&yaml, &config)?, yaml);
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test_should_omit {
+    use super::*;
+
+    #[test]
+    fn phys_bone_paths() {
+        let config = FilterConfig::default();
+        let null = ObjectReference::null();
+        let transform = ObjectReference::local(400000);
+        // foldout_* are omitted only with exact path
+        assert!(should_omit("foldout_transforms", "0", &null, &config));
+        assert!(should_omit("foldout_gizmos", "1", &null, &config));
+        assert!(!should_omit(
+            "foldout_transforms.Array.size",
+            "1",
+            &null,
+            &config
+        ));
+        // arrays of VRCPhysBone are user data
+        for property_path in [
+            "ignoreTransforms.Array.size",
+            "ignoreTransforms.Array.data[0]",
+            "colliders.Array.size",
+            "colliders.Array.data[1]",
+            "limitRotation.x",
+        ] {
+            assert!(
+                !should_omit(property_path, "1", &null, &config),
+                "{}",
+                property_path
+            );
+            assert!(
+                !should_omit(property_path, "", &transform, &config),
+                "{}",
+                property_path
+            );
+        }
+    }
+
+    #[test]
+    fn nested_array_paths() {
+        let config = FilterConfig::default();
+        let null = ObjectReference::null();
+        assert!(should_omit(
+            "DynamicMaterials.Array.size",
+            "1",
+            &null,
+            &config
+        ));
+        assert!(should_omit(
+            "animationHashSet.Array.data[0].hash",
+            "1",
+            &null,
+            &config
+        ));
+        assert!(should_omit(
+            "baseAnimationLayers.Array.data[2].mask",
+            "",
+            &null,
+            &config
+        ));
+        // paths only sharing the prefix or suffix are not omitted
+        assert!(!should_omit(
+            "DynamicMaterialsCache.Array.size",
+            "1",
+            &null,
+            &config
+        ));
+        assert!(!should_omit(
+            "myDynamicMaterials.Array.size",
+            "1",
+            &null,
+            &config
+        ));
+        assert!(!should_omit(
+            "specialAnimationLayers.Array.data[0].mask",
+            "",
+            &null,
+            &config
+        ));
+        assert!(!should_omit(
+            "baseAnimationLayers.Array.data[0].maskName",
+            "",
+            &null,
+            &config
+        ));
+    }
+}
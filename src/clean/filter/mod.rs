@@ -1,3 +1,4 @@
 mod context;
 pub(crate) mod main;
 pub(crate) mod remove_components;
+pub(crate) mod remove_empty_game_objects;
@@ -1,4 +1,4 @@
-use super::context::{Context, ParserResult};
+use super::context::{Context, ParserErr, ParserResult};
 use crate::clean::YamlSection;
 use std::borrow::Cow;
 use std::collections::HashSet;
@@ -34,11 +34,14 @@ pub(in super::super) fn filter(sections: &mut [YamlSection]) -> ParserResult {
     Ok(())
 }
 
-fn filter_yaml(yaml: &str, is_removed: impl Fn(i64) -> bool) -> ParserResult<Cow<str>> {
+pub(in super::super) fn filter_yaml(
+    yaml: &str,
+    is_removed: impl Fn(i64) -> bool,
+) -> ParserResult<Cow<str>> {
     let mut ctx = Context::new(&yaml);
 
     expect_token!(ctx.next()?, StreamStart(_));
-    expect_token!(ctx.next()?, BlockMappingStart);
+    let flow_root = ctx.root_mapping_start()?;
     expect_token!(ctx.next()?, Key);
     let object_type = ctx.next_scalar()?.0;
     expect_token!(ctx.next()?, Value);
@@ -55,8 +58,7 @@ fn filter_yaml(yaml: &str, is_removed: impl Fn(i64) -> bool) -> ParserResult<Cow
     }
 
     // closings
-    assert!(matches!(ctx.next()?, BlockEnd), "BlockEnd expected");
-    assert!(matches!(ctx.next()?, StreamEnd), "StreamEnd expected");
+    ctx.root_mapping_end(flow_root)?;
 
     Ok(ctx.finish().into())
 }
@@ -69,15 +71,23 @@ fn game_object(ctx: &mut Context, is_removed: impl Fn(i64) -> bool) -> ParserRes
         match name.as_str() {
             "serializedVersion" => match ctx.next_scalar()?.0.as_str() {
                 "5" | "6" => {}
-                v => panic!("unknown serializedVersion: {}", v),
+                v => {
+                    return Err(ParserErr::InvalidValue(format!(
+                        "unknown serializedVersion of GameObject: {}",
+                        v
+                    )))
+                }
             },
             "m_Component" => {
                 ctx.write_until_current_token()?;
+                if let FlowSequenceStart = ctx.peek()? {
+                    return flow_components(ctx, &is_removed).map(Continue);
+                }
                 // some elements must be written because Transform is required component
                 ctx.sequence(|ctx| {
                     expect_token!(ctx.next()?, BlockMappingStart);
                     expect_token!(ctx.next()?, Key);
-                    assert_eq!(ctx.next_scalar()?.0, "component");
+                    ctx.expect_key("component")?;
                     expect_token!(ctx.next()?, Value);
                     let reference = ctx.parse_object_reference()?;
                     if reference.is_local() && is_removed(reference.file_id) {
@@ -95,6 +105,41 @@ fn game_object(ctx: &mut Context, is_removed: impl Fn(i64) -> bool) -> ParserRes
     })
 }
 
+/// m_Component in flow style like `[{component: {fileID: 1}}, {component: {fileID: 2}}]`
+fn flow_components(ctx: &mut Context, is_removed: impl Fn(i64) -> bool) -> ParserResult {
+    let mut first = true;
+    let mut kept_any = false;
+    ctx.sequence(|ctx| {
+        // the separator before the element is written with the element
+        // so skip it if there's no element written before
+        if first {
+            ctx.write_until_current_token()?;
+        } else if !kept_any {
+            ctx.skip_until_current_token()?;
+            ctx.skip_trailing_spaces()?;
+        }
+        first = false;
+
+        let mut reference = None;
+        ctx.mapping(|ctx| {
+            ctx.expect_key("component")?;
+            expect_token!(ctx.next()?, Value);
+            reference = Some(ctx.parse_object_reference()?);
+            Ok(Continue(()))
+        })?;
+        let reference = reference.ok_or_else(|| {
+            ParserErr::InvalidValue("component does not exist in m_Component".to_owned())
+        })?;
+        if reference.is_local() && is_removed(reference.file_id) {
+            ctx.skip_until_current_token()?
+        } else {
+            ctx.write_until_current_token()?;
+            kept_any = true;
+        }
+        Ok(Continue(()))
+    })
+}
+
 #[test]
 fn test() -> anyhow::Result<()> {
     assert_eq!(
@@ -143,3 +188,43 @@ fn test() -> anyhow::Result<()> {
     );
     Ok(())
 }
+
+#[test]
+fn flow_style_test() -> anyhow::Result<()> {
+    let yaml = concat!(
+        "GameObject:\n",
+        "  serializedVersion: 6\n",
+        "  m_Component: [{component: {fileID: 1}}, {component: {fileID: 2}}, {component: {fileID: 3}}]\n",
+        "  m_Layer: 0\n",
+        "  m_Name: Text\n",
+    );
+    let filtered = |removed: &'static [i64]| filter_yaml(yaml, |id| removed.contains(&id));
+    let expected = |components: &str| {
+        format!(
+            concat!(
+                "GameObject:\n",
+                "  serializedVersion: 6\n",
+                "  m_Component: [{}]\n",
+                "  m_Layer: 0\n",
+                "  m_Name: Text\n",
+            ),
+            components
+        )
+    };
+    assert_eq!(filtered(&[])?, yaml);
+    assert_eq!(
+        filtered(&[2])?,
+        expected("{component: {fileID: 1}}, {component: {fileID: 3}}")
+    );
+    assert_eq!(
+        filtered(&[1])?,
+        expected("{component: {fileID: 2}}, {component: {fileID: 3}}")
+    );
+    assert_eq!(
+        filtered(&[3])?,
+        expected("{component: {fileID: 1}}, {component: {fileID: 2}}")
+    );
+    assert_eq!(filtered(&[1, 3])?, expected("{component: {fileID: 2}}"));
+    assert_eq!(filtered(&[1, 2, 3])?, expected(""));
+    Ok(())
+}
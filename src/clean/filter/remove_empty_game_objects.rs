@@ -0,0 +1,278 @@
+use super::context::{Context, ParserResult};
+use crate::clean::{collect_local_references, ObjectReference, YamlSection};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::ops::ControlFlow::Continue;
+use yaml_rust::scanner::*;
+use TokenType::*;
+
+/// remove GameObjects which have no components other than Transform after removing components
+/// by the filter and not referenced from anywhere. the Transform of the GameObject will also be
+/// removed. GameObjects originally without other components like markers or grouping objects
+/// are kept. this must be called before `remove_components`.
+pub(in super::super) fn filter(sections: &mut [YamlSection]) -> ParserResult {
+    let index_by_id = sections
+        .iter()
+        .enumerate()
+        .map(|(i, x)| (x.parsed.file_id(), i))
+        .collect::<HashMap<_, _>>();
+    // children of stripped Transform cannot be listed in m_Children of the stripped one
+    // so GameObjects under stripped Transform are reachable even if not referenced.
+    let stripped = sections
+        .iter()
+        .filter(|x| x.parsed.is_stripped() && !x.filtered.is_empty())
+        .map(|x| x.parsed.file_id())
+        .collect::<HashSet<_>>();
+    // local references of each section. same as optimize_yaml, references with guid or type are
+    // references to other files so not included.
+    let mut references = Vec::with_capacity(sections.len());
+    for section in sections.iter() {
+        let mut set = HashSet::new();
+        collect_local_references(&section.filtered, &mut set);
+        references.push(set);
+    }
+
+    for i in 0..sections.len() {
+        if sections[i].filtered.is_empty()
+            || sections[i].parsed.is_stripped()
+            || !sections[i].parsed.may_be_game_object()
+        {
+            continue;
+        }
+        let components = match game_object_components(&sections[i].filtered)? {
+            Some(components) => components,
+            None => continue,
+        };
+        let (removed, kept): (Vec<_>, Vec<_>) = components.iter().partition(|component| {
+            component.is_local()
+                && index_by_id
+                    .get(&component.file_id)
+                    .map(|&j| sections[j].filtered.is_empty())
+                    .unwrap_or(false)
+        });
+        // GameObject always have Transform so single component means only Transform is there
+        if removed.is_empty() || kept.len() != 1 || !kept[0].is_local() {
+            continue;
+        }
+        let transform = match index_by_id.get(&kept[0].file_id) {
+            Some(&transform) => transform,
+            None => continue,
+        };
+        if !references[transform].is_disjoint(&stripped) {
+            continue;
+        }
+
+        // find references except for GameObject and Transform itself
+        let game_object_id = sections[i].parsed.file_id();
+        let transform_id = sections[transform].parsed.file_id();
+        let found = references
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i && j != transform)
+            .any(|(_, x)| x.contains(&game_object_id) || x.contains(&transform_id));
+        if !found {
+            sections[i].filtered = Cow::Borrowed("");
+            sections[transform].filtered = Cow::Borrowed("");
+            references[i].clear();
+            references[transform].clear();
+        }
+    }
+    Ok(())
+}
+
+/// returns components of GameObject or None if the yaml is not a GameObject
+pub(in super::super) fn game_object_components(
+    yaml: &str,
+) -> ParserResult<Option<Vec<ObjectReference>>> {
+    let mut ctx = Context::new(&yaml);
+
+    expect_token!(ctx.next()?, StreamStart(_));
+    ctx.root_mapping_start()?;
+    expect_token!(ctx.next()?, Key);
+    let object_type = ctx.next_scalar()?.0;
+    expect_token!(ctx.next()?, Value);
+    if object_type != "GameObject" {
+        return Ok(None);
+    }
+
+    let mut components = Vec::new();
+    ctx.mapping(|ctx| {
+        let name = ctx.next_scalar()?.0;
+        expect_token!(ctx.next()?, Value);
+        match name.as_str() {
+            "m_Component" => {
+                // m_Component can be block or flow style
+                ctx.sequence(|ctx| {
+                    ctx.mapping(|ctx| {
+                        ctx.expect_key("component")?;
+                        expect_token!(ctx.next()?, Value);
+                        components.push(ctx.parse_object_reference()?);
+                        Ok(Continue(()))
+                    })?;
+                    Ok(Continue(()))
+                })?;
+            }
+            _ => ctx.skip_next_value()?,
+        }
+        Ok(Continue(()))
+    })?;
+
+    Ok(Some(components))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::yaml::ParsedHeadingLine;
+
+    fn sections<'a>(transform_parent: &'a str) -> [YamlSection<'a>; 4] {
+        [
+            YamlSection {
+                heading: "--- !u!1 &1\n",
+                parsed: ParsedHeadingLine::new(1, false),
+                filtered: Cow::Borrowed(concat!(
+                    "GameObject:\n",
+                    "  m_ObjectHideFlags: 0\n",
+                    "  serializedVersion: 6\n",
+                    "  m_Component:\n",
+                    "  - component: {fileID: 2}\n",
+                    "  - component: {fileID: 5}\n",
+                    "  m_Layer: 0\n",
+                    "  m_Name: Empty\n",
+                )),
+            },
+            YamlSection {
+                heading: "--- !u!4 &2\n",
+                parsed: ParsedHeadingLine::new(2, false),
+                filtered: Cow::Borrowed(concat!(
+                    "Transform:\n",
+                    "  m_ObjectHideFlags: 0\n",
+                    "  m_GameObject: {fileID: 1}\n",
+                    "  m_Children: []\n",
+                    "  m_Father: {fileID: 0}\n",
+                )),
+            },
+            YamlSection {
+                heading: "--- !u!4 &3\n",
+                parsed: ParsedHeadingLine::new(3, false),
+                filtered: Cow::Borrowed(transform_parent),
+            },
+            // component removed by the filter
+            YamlSection {
+                heading: "--- !u!114 &5\n",
+                parsed: ParsedHeadingLine::new(5, false),
+                filtered: Cow::Borrowed(""),
+            },
+        ]
+    }
+
+    #[test]
+    fn unreachable() -> anyhow::Result<()> {
+        let mut sections = sections(concat!(
+            "Transform:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_GameObject: {fileID: 4}\n",
+            "  m_Children: []\n",
+            "  m_Father: {fileID: 0}\n",
+        ));
+        filter(&mut sections)?;
+        assert_eq!(sections[0].filtered, "");
+        assert_eq!(sections[1].filtered, "");
+        assert_ne!(sections[2].filtered, "");
+        Ok(())
+    }
+
+    #[test]
+    fn flow_style_components() -> anyhow::Result<()> {
+        let mut sections = sections(concat!(
+            "Transform:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_GameObject: {fileID: 4}\n",
+            "  m_Children: []\n",
+            "  m_Father: {fileID: 0}\n",
+        ));
+        sections[0].filtered = Cow::Borrowed(concat!(
+            "GameObject:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 6\n",
+            "  m_Component: [{component: {fileID: 2}}, {component: {fileID: 5}}]\n",
+            "  m_Layer: 0\n",
+            "  m_Name: Empty\n",
+        ));
+        filter(&mut sections)?;
+        assert_eq!(sections[0].filtered, "");
+        assert_eq!(sections[1].filtered, "");
+        assert_ne!(sections[2].filtered, "");
+        Ok(())
+    }
+
+    #[test]
+    fn reachable() -> anyhow::Result<()> {
+        let mut sections = sections(concat!(
+            "Transform:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_GameObject: {fileID: 4}\n",
+            "  m_Children:\n",
+            "  - {fileID: 2}\n",
+            "  m_Father: {fileID: 0}\n",
+        ));
+        filter(&mut sections)?;
+        assert_ne!(sections[0].filtered, "");
+        assert_ne!(sections[1].filtered, "");
+        assert_ne!(sections[2].filtered, "");
+        Ok(())
+    }
+
+    #[test]
+    fn references_by_tokens() -> anyhow::Result<()> {
+        // reference with other spacing is found
+        let mut spaced = sections(concat!(
+            "Transform:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_GameObject: {fileID: 4}\n",
+            "  m_Children:\n",
+            "  - {fileID:   2 }\n",
+            "  m_Father: {fileID: 0}\n",
+        ));
+        filter(&mut spaced)?;
+        assert_ne!(spaced[0].filtered, "");
+        assert_ne!(spaced[1].filtered, "");
+
+        // text in string is not a reference
+        let mut quoted = sections(concat!(
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_Name: '{fileID: 2}'\n",
+        ));
+        filter(&mut quoted)?;
+        assert_eq!(quoted[0].filtered, "");
+        assert_eq!(quoted[1].filtered, "");
+        Ok(())
+    }
+
+    #[test]
+    fn originally_empty() -> anyhow::Result<()> {
+        let mut sections = sections(concat!(
+            "Transform:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_GameObject: {fileID: 4}\n",
+            "  m_Children: []\n",
+            "  m_Father: {fileID: 0}\n",
+        ));
+        // root GameObject without components like markers must be kept
+        let original = concat!(
+            "GameObject:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 6\n",
+            "  m_Component:\n",
+            "  - component: {fileID: 2}\n",
+            "  m_Layer: 0\n",
+            "  m_Name: Marker\n",
+        );
+        sections[0].filtered = Cow::Borrowed(original);
+        filter(&mut sections)?;
+        assert_eq!(sections[0].filtered, original);
+        assert_ne!(sections[1].filtered, "");
+        Ok(())
+    }
+}
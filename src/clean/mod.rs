@@ -1,9 +1,16 @@
-use crate::yaml::{ParsedHeadingLine, YamlSeparated};
-use log::trace;
+use crate::config::{CleanConfig, Config, CONFIG_FILE_NAME};
+use crate::yaml::{LineEndings, ParsedHeadingLine, YamlSeparated};
+use log::{trace, warn};
 use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::io::Read;
 use std::io::{stdin, stdout, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::str::FromStr;
+use yaml_rust::scanner::*;
+use TokenType::*;
 
 macro_rules! expect_token {
     ($token: expr, $($expect: tt)*) => {
@@ -16,14 +23,19 @@ macro_rules! expect_token {
 
 macro_rules! unexpected_token {
     ($token: expr) => {
-        panic!("unexpected token: {:?}", $token)
+        return Err(crate::clean::filter::context::ParserErr::InvalidValue(
+            format!("unexpected token: {:?}", $token),
+        ))
     };
     ($token: expr, $expected: expr) => {
-        panic!("expected {} but was {:?}", $expected, $token)
+        return Err(crate::clean::filter::context::ParserErr::InvalidValue(
+            format!("expected {} but was {:?}", $expected, $token),
+        ))
     };
 }
 
 mod filter;
+mod streaming;
 
 #[derive(clap::Parser)]
 /// clean file.
@@ -32,27 +44,256 @@ pub(crate) struct App {
     file: Option<String>,
     #[clap(long = "sort")]
     sort: bool,
+    /// remove GameObjects without components other than Transform if not referenced.
+    #[clap(long = "remove-empty-game-objects")]
+    remove_empty_game_objects: bool,
+    /// replace m_IndirectSpecularColor only if it's within this tolerance from the default.
+    #[clap(long = "indirect-specular-color-tolerance")]
+    indirect_specular_color_tolerance: Option<f32>,
+    /// normalize empty m_EditorClassIdentifier to the form without trailing whitespace.
+    #[clap(long = "normalize-editor-class-identifier")]
+    normalize_editor_class_identifier: bool,
+    /// normalize ViewPosition of VRCAvatarDescriptor if it's nearly the default value.
+    #[clap(long = "normalize-view-position")]
+    normalize_view_position: bool,
+    /// normalize -0 in m_LocalPosition and m_LocalScale of Transform to 0.
+    #[clap(long = "normalize-negative-zero")]
+    normalize_negative_zero: bool,
+    /// trim trailing spaces of quoted m_Name of GameObject and MonoBehaviour.
+    #[clap(long = "trim-name")]
+    trim_name: bool,
+    /// remove `.Array.size` modifications of prefabs without `.Array.data[]` modifications.
+    #[clap(long = "remove-orphan-array-size")]
+    remove_orphan_array_size: bool,
+    /// normalize null animatorController in baseAnimationLayers of VRCAvatarDescriptor.
+    #[clap(long = "normalize-default-animator-controller")]
+    normalize_default_animator_controller: bool,
+    /// normalize shapes of automatic collider_* of VRCAvatarDescriptor.
+    #[clap(long = "normalize-auto-colliders")]
+    normalize_auto_colliders: bool,
+    /// normalize m_AmbientProbe of RenderSettings unless the ambient mode is Custom.
+    #[clap(long = "normalize-ambient-probe")]
+    normalize_ambient_probe: bool,
+    /// sort m_RemovedComponents of PrefabInstance by fileID.
+    #[clap(long = "sort-removed-components")]
+    sort_removed_components: bool,
+    /// normalize null references of VRCStation and VRCAvatarPedestal to `{fileID: 0}`.
+    #[clap(long = "normalize-station-null-references")]
+    normalize_station_null_references: bool,
+    /// clean sections without reading whole file. this is ignored if sort or
+    /// remove-empty-game-objects is enabled since those need whole file.
+    #[clap(long = "streaming")]
+    streaming: bool,
+    /// keep headings of removed sections with empty body to see which sections are removed.
+    #[clap(long = "keep-empty-sections")]
+    keep_empty_sections: bool,
+    /// set of rules to apply. objects-only removes computed objects but keeps all fields.
+    #[clap(long = "profile", arg_enum, default_value = "default")]
+    profile: Profile,
+    /// the top of the working tree. resolved once on run
+    #[clap(skip)]
+    root: Option<PathBuf>,
+    /// attributes of the file. queried at once on the first use
+    #[clap(skip)]
+    attributes: RefCell<Option<HashMap<String, String>>>,
+}
+
+/// git attributes used by clean
+const ATTRIBUTES: &[&str] = &[
+    "filter",
+    "unity-sort",
+    "git-vrc-stamp",
+    "git-vrc-remove-empty-gameobjects",
+];
+
+#[derive(clap::ArgEnum, Copy, Clone, Eq, PartialEq, Debug)]
+enum Profile {
+    Default,
+    ObjectsOnly,
 }
 
 impl App {
-    pub(crate) fn run(self) -> anyhow::Result<()> {
-        let mut yaml = String::new();
-        let mut stdin = stdin();
+    pub(crate) fn run(mut self) -> anyhow::Result<()> {
+        self.check_file()?;
+        self.root = crate::git::repo_root();
+        self.apply_config(&load_config(self.root.as_deref()).clean);
+        self.remove_empty_game_objects = self.should_remove_empty_game_objects();
+        let stdout = stdout();
+        let mut stdout = stdout.lock();
+        self.clean(&mut stdin(), &mut stdout)?;
+        stdout.flush()?;
+        Ok(())
+    }
+
+    /// checks the path passed via `--file` can be a file cleaned by git.
+    fn check_file(&self) -> anyhow::Result<()> {
+        if let Some(path) = &self.file {
+            if std::path::Path::new(path).is_dir() {
+                anyhow::bail!(
+                    "{} is a directory. --file must be the path of the file to be cleaned",
+                    path
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// applies values in config file. options specified via command line are preferred.
+    fn apply_config(&mut self, config: &CleanConfig) {
+        self.sort |= config.sort.unwrap_or(false);
+        self.remove_empty_game_objects |= config.remove_empty_game_objects.unwrap_or(false);
+        self.indirect_specular_color_tolerance = self
+            .indirect_specular_color_tolerance
+            .or(config.indirect_specular_color_tolerance);
+        self.normalize_editor_class_identifier |=
+            config.normalize_editor_class_identifier.unwrap_or(false);
+        self.normalize_view_position |= config.normalize_view_position.unwrap_or(false);
+        self.normalize_negative_zero |= config.normalize_negative_zero.unwrap_or(false);
+        self.trim_name |= config.trim_name.unwrap_or(false);
+        self.remove_orphan_array_size |= config.remove_orphan_array_size.unwrap_or(false);
+        self.normalize_default_animator_controller |= config
+            .normalize_default_animator_controller
+            .unwrap_or(false);
+        self.normalize_auto_colliders |= config.normalize_auto_colliders.unwrap_or(false);
+        self.normalize_ambient_probe |= config.normalize_ambient_probe.unwrap_or(false);
+        self.sort_removed_components |= config.sort_removed_components.unwrap_or(false);
+        self.normalize_station_null_references |=
+            config.normalize_station_null_references.unwrap_or(false);
+    }
+
+    fn clean(&self, input: &mut impl Read, output: &mut impl Write) -> anyhow::Result<()> {
+        let filter = self.filter_attribute();
+        if filter.as_deref().map(is_filter_overridden).unwrap_or(false) {
+            // filter for the file is overridden by user. work as copy
+            std::io::copy(input, output)?;
+            return Ok(());
+        }
+
         const HEADER: &[u8] = b"%YAML";
-        let mut heading = [0u8; HEADER.len()];
-        stdin.read_exact(&mut heading)?;
-        if heading != HEADER {
+        const SEPARATOR: &[u8] = b"---";
+        const LFS_POINTER: &[u8] = b"version https://git-lfs.github.com/spec/";
+        // the first KB is used to detect binary content
+        let mut heading = [0u8; 1024];
+        let read = read_up_to(input, &mut heading)?;
+        let heading = &heading[..read];
+        // git-lfs pointer file must never be parsed
+        let lfs_pointer = heading.starts_with(LFS_POINTER);
+        // binary or compressed file like LightingData.asset must never be parsed
+        let binary = heading.contains(&0);
+        if binary {
+            warn!(
+                "{}: binary content found. the file is not cleaned",
+                self.file.as_deref().unwrap_or("<stdin>")
+            );
+        }
+        // headerless yaml is also cleaned if the file is specified as vrc file
+        if lfs_pointer
+            || binary
+            || !heading.starts_with(HEADER)
+                && !(heading.starts_with(SEPARATOR) && filter.as_deref() == Some("vrc"))
+        {
             // work as copy
-            let mut stdout = stdout();
-            stdout.write(&heading)?;
-            std::io::copy(&mut stdin, &mut stdout)?;
+            output.write_all(heading)?;
+            std::io::copy(input, output)?;
             return Ok(());
         }
-        yaml.push_str(std::str::from_utf8(HEADER).unwrap());
-        stdin.read_to_string(&mut yaml)?;
-        let mut iter = YamlSeparated::new(&yaml);
-        let first = iter.next().unwrap();
-        print!("{}{}", first.0, first.1);
+        let sort = self.should_sort();
+        if self.streaming && !sort && !self.remove_empty_game_objects {
+            // sections can be written without reading whole file
+            let input = std::io::BufReader::new(heading.chain(input));
+            streaming::write_cleaned_yaml(self, input, output)?;
+        } else {
+            let mut yaml = heading.to_vec();
+            input.read_to_end(&mut yaml)?;
+            let yaml = String::from_utf8(yaml)?;
+
+            // stamp is always removed and written again if enabled
+            self.write_cleaned_yaml(strip_stamp(&yaml), sort, output)?;
+        }
+        if self.file_attribute("git-vrc-stamp").as_deref() == Some("set") {
+            // the first KB is enough to detect the line ending of the document
+            let mut line_endings = LineEndings::default();
+            line_endings.add(&String::from_utf8_lossy(heading));
+            write_stamp(output, line_endings.line_ending())?;
+        }
+        Ok(())
+    }
+
+    fn filter_attribute(&self) -> Option<String> {
+        self.file_attribute("filter")
+    }
+
+    fn should_sort(&self) -> bool {
+        match self.file_attribute("unity-sort") {
+            Some(value) => bool_attribute("unity-sort", &value, self.sort),
+            None => self.sort,
+        }
+    }
+
+    fn should_remove_empty_game_objects(&self) -> bool {
+        let attr = "git-vrc-remove-empty-gameobjects";
+        match self.file_attribute(attr) {
+            Some(value) => bool_attribute(attr, &value, self.remove_empty_game_objects),
+            None => self.remove_empty_game_objects,
+        }
+    }
+
+    /// returns the value of the attribute for the file.
+    /// if git cannot get attributes, for example the file is outside of the repository,
+    /// this returns None and the default is used.
+    /// `%f` is relative to the top of the working tree so check-attr runs there
+    /// even if this is invoked in a subdirectory.
+    fn file_attribute(&self, attr: &str) -> Option<String> {
+        self.file_attribute_with(attr, |attrs, targets| {
+            let mut command = Command::new("git");
+            if let Some(root) = &self.root {
+                command.current_dir(root);
+            }
+            crate::git::check_attr_with(command, attrs, targets).map(|result| result.collect())
+        })
+    }
+
+    fn file_attribute_with(
+        &self,
+        attr: &str,
+        check_attr: impl FnOnce(&[&str], &[&str]) -> std::io::Result<Vec<(String, String, String)>>,
+    ) -> Option<String> {
+        debug_assert!(ATTRIBUTES.contains(&attr), "{} is not in ATTRIBUTES", attr);
+        let path = self.file.as_ref()?;
+        let mut attributes = self.attributes.borrow_mut();
+        let attributes =
+            attributes.get_or_insert_with(|| match check_attr(ATTRIBUTES, &[path.as_str()]) {
+                Ok(result) => result
+                    .into_iter()
+                    .map(|(_path, attr, value)| (attr, value))
+                    .collect(),
+                Err(e) => {
+                    warn!("failed to get attributes of {}: {}", path, e);
+                    HashMap::new()
+                }
+            });
+        attributes.get(attr).cloned()
+    }
+
+    fn write_cleaned_yaml(
+        &self,
+        yaml: &str,
+        sort: bool,
+        output: &mut impl Write,
+    ) -> anyhow::Result<()> {
+        let mut line_endings = LineEndings::default();
+        line_endings.add(yaml);
+        if line_endings.is_mixed() {
+            warn_mixed_line_endings(self.file.as_deref());
+        }
+
+        let mut iter = YamlSeparated::new(yaml).peekable();
+
+        // heading element without separator like `%YAML` and `%TAG`
+        if let Some(&("", header)) = iter.peek() {
+            output.write_all(header.as_bytes())?;
+            iter.next();
+        }
 
         // filter phase
         let mut sections = iter
@@ -66,58 +307,1101 @@ impl App {
             })
             .collect::<Result<Vec<_>, _>>()?;
 
-        filter::main::filter(&mut sections)?;
+        filter::main::filter(&mut sections, &self.filter_config())?;
 
         // optimization
         optimize_yaml(&mut sections);
 
-        filter::remove_components::filter(&mut sections)?;
-
-        let mut sort = self.sort;
-        if let Some(path) = self.file {
-            let (_path, _attr, value) = crate::git::check_attr(&["unity-sort"], &[path.as_str()])?
-                .next()
-                .expect("failed to get attr");
-            if value.as_str() == "set" {
-                sort = true
-            }
+        // this needs components before removed from m_Component
+        if self.remove_empty_game_objects {
+            filter::remove_empty_game_objects::filter(&mut sections)?;
         }
 
+        filter::remove_components::filter(&mut sections)?;
+
         if sort {
             sections.sort_by_key(|x| x.parsed.file_id())
         }
 
         for sec in sections {
             if !sec.filtered.is_empty() {
-                print!("{}{}", sec.heading, sec.filtered);
+                write!(output, "{}{}", sec.heading, sec.filtered)?;
+            } else if self.keep_empty_sections {
+                write!(output, "{}", sec.heading)?;
             }
         }
 
         Ok(())
     }
+
+    fn filter_config(&self) -> filter::main::FilterConfig {
+        filter::main::FilterConfig {
+            indirect_specular_color_tolerance: self.indirect_specular_color_tolerance,
+            normalize_editor_class_identifier: self.normalize_editor_class_identifier,
+            normalize_view_position: self.normalize_view_position,
+            normalize_negative_zero: self.normalize_negative_zero,
+            trim_name: self.trim_name,
+            objects_only: self.profile == Profile::ObjectsOnly,
+            remove_orphan_array_size: self.remove_orphan_array_size,
+            normalize_default_animator_controller: self.normalize_default_animator_controller,
+            normalize_auto_colliders: self.normalize_auto_colliders,
+            normalize_ambient_probe: self.normalize_ambient_probe,
+            sort_removed_components: self.sort_removed_components,
+            normalize_station_null_references: self.normalize_station_null_references,
+        }
+    }
+}
+
+#[test]
+fn apply_config_test() -> anyhow::Result<()> {
+    let config = Config::parse(concat!(
+        "[clean]\n",
+        "normalize-view-position = true\n",
+        "indirect-specular-color-tolerance = 0.001\n",
+    ))?;
+    let mut app = <App as clap::Parser>::parse_from(["clean", "--sort"]);
+    app.apply_config(&config.clean);
+    assert!(app.sort);
+    assert!(app.normalize_view_position);
+    assert!(!app.normalize_editor_class_identifier);
+    assert_eq!(app.indirect_specular_color_tolerance, Some(0.001));
+
+    // command line is preferred
+    let mut app =
+        <App as clap::Parser>::parse_from(["clean", "--indirect-specular-color-tolerance", "0.01"]);
+    app.apply_config(&config.clean);
+    assert_eq!(app.indirect_specular_color_tolerance, Some(0.01));
+    Ok(())
+}
+
+/// loads config file at the repository root. malformed config file must not break
+/// git operations like checkout so this warns and uses the default config.
+fn load_config(root: Option<&Path>) -> Config {
+    Config::load(root).unwrap_or_else(|e| {
+        warn!(
+            "failed to load {}. the default config is used: {}",
+            CONFIG_FILE_NAME, e
+        );
+        Config::default()
+    })
+}
+
+#[test]
+fn load_config_test() -> anyhow::Result<()> {
+    let dir = std::env::temp_dir().join(format!(
+        "git-vrc-test-clean-load-config-{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join(CONFIG_FILE_NAME), "[clean]\nsort = true\n")?;
+    let valid = load_config(Some(&dir));
+    std::fs::write(dir.join(CONFIG_FILE_NAME), "[clean]\nunknown-rule = true\n")?;
+    let malformed = load_config(Some(&dir));
+    std::fs::remove_dir_all(&dir)?;
+
+    assert_eq!(valid.clean.sort, Some(true));
+    assert_eq!(malformed, Config::default());
+    Ok(())
+}
+
+const STAMP_PREFIX: &str = "# cleaned by git-vrc ";
+
+/// mixed line endings are usually made by bad merges. those are kept as is.
+fn warn_mixed_line_endings(file: Option<&str>) {
+    warn!(
+        "{}: mixed line endings (LF and CRLF) found. line endings are kept as is",
+        file.unwrap_or("<stdin>")
+    );
+}
+
+/// writes comment line to record the version of git-vrc cleaned the file
+fn write_stamp(output: &mut impl Write, line_ending: &str) -> std::io::Result<()> {
+    write!(
+        output,
+        "{}{}{}",
+        STAMP_PREFIX,
+        env!("CARGO_PKG_VERSION"),
+        line_ending
+    )
+}
+
+/// removes the comment line written by [`write_stamp`] at the end of the yaml
+fn strip_stamp(yaml: &str) -> &str {
+    let body = yaml.strip_suffix('\n').unwrap_or(yaml);
+    match body.rfind('\n') {
+        Some(i) if body[i + 1..].starts_with(STAMP_PREFIX) => &yaml[..i + 1],
+        _ => yaml,
+    }
+}
+
+#[test]
+fn stamp_test() -> anyhow::Result<()> {
+    let app = <App as clap::Parser>::parse_from(["clean"]);
+    let clean = |yaml: &str| -> anyhow::Result<String> {
+        let mut output = Vec::<u8>::new();
+        app.write_cleaned_yaml(strip_stamp(yaml), false, &mut output)?;
+        write_stamp(&mut output, "\n")?;
+        Ok(String::from_utf8(output)?)
+    };
+    let yaml = concat!(
+        "%YAML 1.1\n",
+        "%TAG !u! tag:unity3d.com,2011:\n",
+        "--- !u!1 &1\n",
+        "GameObject:\n",
+        "  m_ObjectHideFlags: 0\n",
+        "  m_Name: Object\n",
+    );
+    let stamped = format!(
+        "{}# cleaned by git-vrc {}\n",
+        yaml,
+        env!("CARGO_PKG_VERSION")
+    );
+
+    assert_eq!(clean(yaml)?, stamped);
+    // re-cleaning is idempotent
+    assert_eq!(clean(&stamped)?, stamped);
+    // stamp of other version is replaced
+    assert_eq!(
+        clean(&format!("{}# cleaned by git-vrc 0.0.0\n", yaml))?,
+        stamped
+    );
+    // stamp is removed if disabled
+    assert_eq!(strip_stamp(&stamped), yaml);
+
+    // stamp uses the line ending of the document
+    let app = <App as clap::Parser>::parse_from(["clean", "--file", "Assets/Object.prefab"]);
+    *app.attributes.borrow_mut() = Some(HashMap::from([(
+        "git-vrc-stamp".to_owned(),
+        "set".to_owned(),
+    )]));
+    let crlf = yaml.replace('\n', "\r\n");
+    let mut output = Vec::<u8>::new();
+    app.clean(&mut crlf.as_bytes(), &mut output)?;
+    assert_eq!(String::from_utf8(output)?, stamped.replace('\n', "\r\n"));
+    Ok(())
+}
+
+/// reads bytes until the buffer is filled or reached to EOF. returns the count of read bytes.
+fn read_up_to(input: &mut impl Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut read = 0;
+    while read < buf.len() {
+        match input.read(&mut buf[read..]) {
+            Ok(0) => break,
+            Ok(n) => read += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(read)
+}
+
+/// returns whether the rule is enabled or not with the value of boolean attribute like unity-sort
+fn bool_attribute(attr: &str, value: &str, default: bool) -> bool {
+    match value {
+        "set" => true,
+        "unset" => false,
+        "unspecified" => default,
+        unknown => {
+            warn!("unknown value for {} attribute: {}", attr, unknown);
+            default
+        }
+    }
+}
+
+#[test]
+fn bool_attribute_test() {
+    assert!(bool_attribute("unity-sort", "set", false));
+    assert!(bool_attribute("unity-sort", "set", true));
+    assert!(!bool_attribute("unity-sort", "unset", false));
+    assert!(!bool_attribute("unity-sort", "unset", true));
+    assert!(!bool_attribute("unity-sort", "unspecified", false));
+    assert!(bool_attribute("unity-sort", "unspecified", true));
+}
+
+/// returns true if filter attribute is configured to other than vrc like `-filter` or `filter=lfs`
+fn is_filter_overridden(filter: &str) -> bool {
+    !matches!(filter, "vrc" | "set" | "unspecified")
+}
+
+#[test]
+fn is_filter_overridden_test() {
+    assert!(!is_filter_overridden("vrc"));
+    assert!(!is_filter_overridden("unspecified"));
+    assert!(is_filter_overridden("unset"));
+    assert!(is_filter_overridden("lfs"));
+}
+
+#[test]
+fn clean_test() -> anyhow::Result<()> {
+    let app = <App as clap::Parser>::parse_from(["clean"]);
+    let clean = |input: &str| -> anyhow::Result<String> {
+        let mut output = Vec::<u8>::new();
+        app.clean(&mut input.as_bytes(), &mut output)?;
+        Ok(String::from_utf8(output)?)
+    };
+
+    // with %YAML header
+    assert_eq!(
+        clean(concat!(
+            "%YAML 1.1\n",
+            "%TAG !u! tag:unity3d.com,2011:\n",
+            "--- !u!114 &1\n",
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  fallbackStatus: 3\n",
+            "--- !u!1 &2\n",
+            "GameObject:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_Name: Object\n",
+        ))?,
+        concat!(
+            "%YAML 1.1\n",
+            "%TAG !u! tag:unity3d.com,2011:\n",
+            "--- !u!114 &1\n",
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  fallbackStatus: 0\n",
+            "--- !u!1 &2\n",
+            "GameObject:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_Name: Object\n",
+        )
+    );
+
+    let headerless = concat!(
+        "--- !u!114 &1\n",
+        "MonoBehaviour:\n",
+        "  m_ObjectHideFlags: 0\n",
+        "  fallbackStatus: 3\n",
+        "--- !u!1 &2\n",
+        "GameObject:\n",
+        "  m_ObjectHideFlags: 0\n",
+        "  m_Name: Object\n",
+    );
+
+    // without %YAML header and attributes: copied
+    assert_eq!(clean(headerless)?, headerless);
+
+    // files shorter than the header: copied
+    assert_eq!(clean("ab")?, "ab");
+    assert_eq!(clean("")?, "");
+
+    // git-lfs pointer: copied
+    let lfs_pointer = concat!(
+        "version https://git-lfs.github.com/spec/v1\n",
+        "oid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\n",
+        "size 12345\n",
+    );
+    assert_eq!(clean(lfs_pointer)?, lfs_pointer);
+
+    // binary content: copied even if it starts with %YAML
+    let binary = b"%YAML 1.1\n--- !u!1 &1\nGameObject:\n\0\x1f\x8b\x08\0binary";
+    let mut output = Vec::<u8>::new();
+    app.clean(&mut &binary[..], &mut output)?;
+    assert_eq!(output, binary);
+    // gzip header
+    let gzip = b"\x1f\x8b\x08\0\0\0\0\0\0\x03compressed";
+    let mut output = Vec::<u8>::new();
+    app.clean(&mut &gzip[..], &mut output)?;
+    assert_eq!(output, gzip);
+
+    // mixed line endings: cleaned and line endings are kept
+    assert_eq!(
+        clean(concat!(
+            "%YAML 1.1\r\n",
+            "%TAG !u! tag:unity3d.com,2011:\n",
+            "--- !u!114 &1\r\n",
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\r\n",
+            "  fallbackStatus: 3\r\n",
+            "--- !u!1 &2\n",
+            "GameObject:\r\n",
+            "  m_Name: Object\n",
+        ))?,
+        concat!(
+            "%YAML 1.1\r\n",
+            "%TAG !u! tag:unity3d.com,2011:\n",
+            "--- !u!114 &1\r\n",
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\r\n",
+            "  fallbackStatus: 0\r\n",
+            "--- !u!1 &2\n",
+            "GameObject:\r\n",
+            "  m_Name: Object\n",
+        ),
+    );
+
+    // header and multibyte character around the end of the heading read
+    let yaml = format!(
+        "%YAML 1.1\n--- !u!1 &1\nGameObject:\n  a: {}あいうえお\n",
+        "x".repeat(1023 - 39)
+    );
+    assert_eq!(clean(&yaml)?, yaml);
+
+    // header split into short reads
+    let yaml = concat!(
+        "%YAML 1.1\n",
+        "%TAG !u! tag:unity3d.com,2011:\n",
+        "--- !u!114 &1\n",
+        "MonoBehaviour:\n",
+        "  fallbackStatus: 3\n",
+    );
+    let mut output = Vec::<u8>::new();
+    app.clean(
+        &mut yaml[..2].as_bytes().chain(yaml[2..].as_bytes()),
+        &mut output,
+    )?;
+    assert_eq!(String::from_utf8(output)?, clean(yaml)?);
+
+    // without %YAML header for vrc file
+    let mut output = Vec::<u8>::new();
+    app.write_cleaned_yaml(headerless, false, &mut output)?;
+    assert_eq!(
+        String::from_utf8(output)?,
+        concat!(
+            "--- !u!114 &1\n",
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  fallbackStatus: 0\n",
+            "--- !u!1 &2\n",
+            "GameObject:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_Name: Object\n",
+        )
+    );
+    Ok(())
+}
+
+/// runs all phases of clean with sorting on the yaml document
+#[cfg(test)]
+fn clean_document(yaml: &str) -> anyhow::Result<String> {
+    let app = <App as clap::Parser>::parse_from(["clean", "--sort"]);
+    let mut output = Vec::<u8>::new();
+    app.write_cleaned_yaml(yaml, app.sort, &mut output)?;
+    Ok(String::from_utf8(output)?)
+}
+
+#[test]
+fn header_only_test() -> anyhow::Result<()> {
+    // header without any section is kept as is
+    let header = "%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n";
+    assert_eq!(clean_document(header)?, header);
+    assert_eq!(clean_document("%YAML 1.1")?, "%YAML 1.1");
+    assert_eq!(clean_document("")?, "");
+
+    let app = <App as clap::Parser>::parse_from(["clean"]);
+    let mut output = Vec::<u8>::new();
+    app.clean(&mut header.as_bytes(), &mut output)?;
+    assert_eq!(String::from_utf8(output)?, header);
+    Ok(())
+}
+
+#[test]
+fn empty_section_test() -> anyhow::Result<()> {
+    // sections without body are treated same as removed sections
+    assert_eq!(
+        clean_document(concat!(
+            "%YAML 1.1\n",
+            "%TAG !u! tag:unity3d.com,2011:\n",
+            "--- !u!1 &1\n",
+            "GameObject:\n",
+            "  m_Name: Object\n",
+            "--- !u!1 &2\n",
+        ))?,
+        concat!(
+            "%YAML 1.1\n",
+            "%TAG !u! tag:unity3d.com,2011:\n",
+            "--- !u!1 &1\n",
+            "GameObject:\n",
+            "  m_Name: Object\n",
+        ),
+    );
+    Ok(())
+}
+
+#[test]
+fn first_section_test() -> anyhow::Result<()> {
+    // the first section just after the header must be cleaned like others
+    assert_eq!(
+        clean_document(concat!(
+            "%YAML 1.1\n",
+            "%TAG !u! tag:unity3d.com,2011:\n",
+            "--- !u!104 &2\n",
+            "RenderSettings:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 9\n",
+            "  m_IndirectSpecularColor: {r: 0.18028305, g: 0.22571313, b: 0.3069213, a: 1}\n",
+            "  m_UseRadianceAmbientProbe: 0\n",
+            "--- !u!157 &3\n",
+            "LightmapSettings:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 12\n",
+        ))?,
+        concat!(
+            "%YAML 1.1\n",
+            "%TAG !u! tag:unity3d.com,2011:\n",
+            "--- !u!104 &2\n",
+            "RenderSettings:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 9\n",
+            "  m_IndirectSpecularColor: {r: 0, g: 0, b: 0, a: 1}\n",
+            "  m_UseRadianceAmbientProbe: 0\n",
+            "--- !u!157 &3\n",
+            "LightmapSettings:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 12\n",
+        ),
+    );
+    Ok(())
+}
+
+#[test]
+fn avatar_fixture_test() -> anyhow::Result<()> {
+    // avatar exported with VRChat SDK 3.x
+    let avatar = include_str!("../../tests/fixtures/avatar.prefab");
+    let cleaned = include_str!("../../tests/fixtures/avatar.cleaned.prefab");
+    assert_eq!(clean_document(avatar)?, cleaned);
+    assert_eq!(clean_document(cleaned)?, cleaned);
+    Ok(())
+}
+
+#[test]
+fn mutated_fixture_test() {
+    // cleaning mutated unity yaml returns error instead of panicking.
+    let apps = [
+        <App as clap::Parser>::parse_from(["clean", "--sort"]),
+        <App as clap::Parser>::parse_from(["clean", "--remove-empty-game-objects"]),
+    ];
+    let check = |yaml: &str| {
+        for app in &apps {
+            let _ = app.write_cleaned_yaml(yaml, app.sort, &mut Vec::<u8>::new());
+        }
+    };
+
+    let seeds = [
+        include_str!("../../tests/fixtures/avatar.prefab"),
+        include_str!("../../tests/fixtures/variant.prefab"),
+    ];
+    let fragments = [
+        "\n",
+        "---",
+        "\n--- !u!1 &",
+        " stripped",
+        "{",
+        "}",
+        "[",
+        "]",
+        ":",
+        ", ",
+        "- ",
+        "\"",
+        "\u{3042}",
+    ];
+
+    for seed in seeds {
+        // every 7th position to keep the test fast enough
+        for (pos, _) in seed.char_indices().step_by(7) {
+            check(&seed[..pos]);
+            for fragment in fragments {
+                check(&format!("{}{}{}", &seed[..pos], fragment, &seed[pos..]));
+            }
+        }
+    }
+}
+
+#[test]
+fn pipeline_saver_references_test() -> anyhow::Result<()> {
+    assert_eq!(
+        clean_document(concat!(
+            "%YAML 1.1\n",
+            "%TAG !u! tag:unity3d.com,2011:\n",
+            "--- !u!114 &3\n",
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_GameObject: {fileID: 1}\n",
+            "  m_Script: {fileID: 229740497, guid: 4ecd63eff847044b68db9453ce219299, type: 3}\n",
+            "  m_Name:\n",
+            "  target: {fileID: 4}\n",
+            "--- !u!1 &1\n",
+            "GameObject:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 6\n",
+            "  m_Component:\n",
+            "  - component: {fileID: 2}\n",
+            "  - component: {fileID: 3}\n",
+            "  m_Name: Object\n",
+            "--- !u!4 &2\n",
+            "Transform:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_GameObject: {fileID: 1}\n",
+            "  m_Children: []\n",
+            "  m_Father: {fileID: 0}\n",
+            "--- !u!114 &4 stripped\n",
+            "MonoBehaviour:\n",
+            "  m_CorrespondingSourceObject: {fileID: 11, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+            "  m_PrefabInstance: {fileID: 5}\n",
+            "  m_PrefabAsset: {fileID: 0}\n",
+        ))?,
+        concat!(
+            "%YAML 1.1\n",
+            "%TAG !u! tag:unity3d.com,2011:\n",
+            "--- !u!1 &1\n",
+            "GameObject:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 6\n",
+            "  m_Component:\n",
+            "  - component: {fileID: 2}\n",
+            "  m_Name: Object\n",
+            "--- !u!4 &2\n",
+            "Transform:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_GameObject: {fileID: 1}\n",
+            "  m_Children: []\n",
+            "  m_Father: {fileID: 0}\n",
+        ),
+    );
+    Ok(())
+}
+
+#[test]
+fn crlf_test() -> anyhow::Result<()> {
+    // with core.autocrlf, clean receives lf but with eol attributes it may receive crlf.
+    // both should be cleaned in the same way.
+    let lf = concat!(
+        "%YAML 1.1\n",
+        "%TAG !u! tag:unity3d.com,2011:\n",
+        "--- !u!1 &1\n",
+        "GameObject:\n",
+        "  m_ObjectHideFlags: 0\n",
+        "  serializedVersion: 6\n",
+        "  m_Component:\n",
+        "  - component: {fileID: 2}\n",
+        "  - component: {fileID: 3}\n",
+        "  m_Name: Object\n",
+        "--- !u!114 &2\n",
+        "MonoBehaviour:\n",
+        "  m_ObjectHideFlags: 0\n",
+        "  m_GameObject: {fileID: 1}\n",
+        "  m_Script: {fileID: -1427037861, guid: 4ecd63eff847044b68db9453ce219299, type: 3}\n",
+        "  m_Name:\n",
+        "  fallbackStatus: 3\n",
+        "  completedSDKPipeline: 1\n",
+        "--- !u!114 &3\n",
+        "MonoBehaviour:\n",
+        "  m_ObjectHideFlags: 0\n",
+        "  m_GameObject: {fileID: 1}\n",
+        "  m_Script: {fileID: 229740497, guid: 4ecd63eff847044b68db9453ce219299, type: 3}\n",
+        "  m_Name:\n",
+        "--- !u!114 &4 stripped\n",
+        "MonoBehaviour:\n",
+        "  m_PrefabInstance: {fileID: 5}\n",
+    );
+    let cleaned = clean_document(lf)?;
+    assert_ne!(cleaned, lf);
+    assert_eq!(
+        clean_document(&lf.replace('\n', "\r\n"))?,
+        cleaned.replace('\n', "\r\n")
+    );
+    Ok(())
+}
+
+#[test]
+fn file_attribute_once_test() {
+    let app = <App as clap::Parser>::parse_from(["clean", "--file", "Assets/Scene.unity"]);
+    let calls = std::cell::Cell::new(0);
+    for attr in ATTRIBUTES {
+        let value = app.file_attribute_with(attr, |attrs, targets| {
+            calls.set(calls.get() + 1);
+            Ok(attrs
+                .iter()
+                .map(|attr| (targets[0].to_owned(), attr.to_string(), "set".to_owned()))
+                .collect())
+        });
+        assert_eq!(value.as_deref(), Some("set"));
+    }
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn file_attribute_from_subdirectory_test() -> anyhow::Result<()> {
+    let dir = std::env::temp_dir().join(format!(
+        "git-vrc-test-file-attribute-from-subdirectory-{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    let subdirectory = dir.join("sub").join("dir");
+    std::fs::create_dir_all(&subdirectory)?;
+    let status = Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(&dir)
+        .status()?;
+    assert!(status.success());
+    std::fs::write(dir.join(".gitattributes"), "sub/dir/*.prefab unity-sort\n")?;
+
+    // `git vrc clean --file sub/dir/x.prefab` in sub/dir
+    let mut app = <App as clap::Parser>::parse_from(["clean", "--file", "sub/dir/x.prefab"]);
+    let mut command = Command::new("git");
+    command.current_dir(&subdirectory);
+    app.root = crate::git::repo_root_with(command);
+    let sort = app.file_attribute("unity-sort");
+    std::fs::remove_dir_all(&dir)?;
+
+    assert_eq!(sort.as_deref(), Some("set"));
+    Ok(())
+}
+
+#[test]
+fn file_outside_repository_test() {
+    let file = std::env::temp_dir().join(format!(
+        "git-vrc-test-outside-repository-{}.asset",
+        std::process::id()
+    ));
+    let file = file.to_str().unwrap();
+    let app = <App as clap::Parser>::parse_from(["clean", "--sort", "--file", file]);
+    assert_eq!(app.filter_attribute(), None);
+    assert!(app.should_sort());
+}
+
+#[test]
+fn file_is_directory_test() {
+    let dir = std::env::temp_dir();
+    let dir = dir.to_str().unwrap();
+    let app = <App as clap::Parser>::parse_from(["clean", "--file", dir]);
+    let error = app.check_file().unwrap_err().to_string();
+    assert!(error.contains("is a directory"), "{}", error);
+
+    let app = <App as clap::Parser>::parse_from(["clean", "--file", "Assets/Scene.unity"]);
+    assert!(app.check_file().is_ok());
+}
+
+#[test]
+fn remove_empty_game_objects_cascade_test() -> anyhow::Result<()> {
+    // the GameObject becomes empty after the PipelineSaver is removed
+    let yaml = concat!(
+        "--- !u!1 &1\n",
+        "GameObject:\n",
+        "  m_Component:\n",
+        "  - component: {fileID: 2}\n",
+        "  - component: {fileID: 3}\n",
+        "  m_Name: Object\n",
+        "--- !u!4 &2\n",
+        "Transform:\n",
+        "  m_GameObject: {fileID: 1}\n",
+        "--- !u!114 &3\n",
+        "MonoBehaviour:\n",
+        "  m_GameObject: {fileID: 1}\n",
+        "  m_Script: {fileID: 229740497, guid: 4ecd63eff847044b68db9453ce219299, type: 3}\n",
+    );
+    let clean = |args: &[&str]| -> anyhow::Result<String> {
+        let app = <App as clap::Parser>::parse_from(args);
+        let mut output = Vec::<u8>::new();
+        app.write_cleaned_yaml(yaml, false, &mut output)?;
+        Ok(String::from_utf8(output)?)
+    };
+
+    assert_eq!(
+        clean(&["clean"])?,
+        concat!(
+            "--- !u!1 &1\n",
+            "GameObject:\n",
+            "  m_Component:\n",
+            "  - component: {fileID: 2}\n",
+            "  m_Name: Object\n",
+            "--- !u!4 &2\n",
+            "Transform:\n",
+            "  m_GameObject: {fileID: 1}\n",
+        )
+    );
+    assert_eq!(clean(&["clean", "--remove-empty-game-objects"])?, "");
+
+    // the GameObject originally without components is kept
+    let marker = concat!(
+        "--- !u!1 &1\n",
+        "GameObject:\n",
+        "  m_Component:\n",
+        "  - component: {fileID: 2}\n",
+        "  m_Name: Marker\n",
+        "--- !u!4 &2\n",
+        "Transform:\n",
+        "  m_GameObject: {fileID: 1}\n",
+    );
+    let app = <App as clap::Parser>::parse_from(["clean", "--remove-empty-game-objects"]);
+    let mut output = Vec::<u8>::new();
+    app.write_cleaned_yaml(marker, false, &mut output)?;
+    assert_eq!(String::from_utf8(output)?, marker);
+    Ok(())
+}
+
+#[test]
+fn keep_empty_sections_test() -> anyhow::Result<()> {
+    let yaml = concat!(
+        "--- !u!114 &2\n",
+        "MonoBehaviour:\n",
+        "  m_ObjectHideFlags: 0\n",
+        "  m_Script: {fileID: 229740497, guid: 4ecd63eff847044b68db9453ce219299, type: 3}\n",
+        "--- !u!1 &3\n",
+        "GameObject:\n",
+        "  m_Name: Object\n",
+    );
+    let clean = |args: &[&str]| -> anyhow::Result<String> {
+        let app = <App as clap::Parser>::parse_from(args);
+        let mut output = Vec::<u8>::new();
+        app.write_cleaned_yaml(yaml, false, &mut output)?;
+        Ok(String::from_utf8(output)?)
+    };
+
+    assert_eq!(
+        clean(&["clean"])?,
+        "--- !u!1 &3\nGameObject:\n  m_Name: Object\n"
+    );
+    assert_eq!(
+        clean(&["clean", "--keep-empty-sections"])?,
+        "--- !u!114 &2\n--- !u!1 &3\nGameObject:\n  m_Name: Object\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn objects_only_profile_test() -> anyhow::Result<()> {
+    let app = <App as clap::Parser>::parse_from(["clean", "--profile", "objects-only"]);
+    let mut output = Vec::<u8>::new();
+    app.write_cleaned_yaml(
+        concat!(
+            "--- !u!1 &1\n",
+            "GameObject:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 6\n",
+            "  m_Component:\n",
+            "  - component: {fileID: 2}\n",
+            "  - component: {fileID: 3}\n",
+            "  m_Name: Object\n",
+            "--- !u!114 &2\n",
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_GameObject: {fileID: 1}\n",
+            "  m_Script: {fileID: 229740497, guid: 4ecd63eff847044b68db9453ce219299, type: 3}\n",
+            "  m_Name:\n",
+            "--- !u!114 &3\n",
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_GameObject: {fileID: 1}\n",
+            "  m_Script: {fileID: -1427037861, guid: 4ecd63eff847044b68db9453ce219299, type: 3}\n",
+            "  m_Name:\n",
+            "  fallbackStatus: 3\n",
+            "  completedSDKPipeline: 1\n",
+        ),
+        false,
+        &mut output,
+    )?;
+    assert_eq!(
+        String::from_utf8(output)?,
+        concat!(
+            "--- !u!1 &1\n",
+            "GameObject:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 6\n",
+            "  m_Component:\n",
+            "  - component: {fileID: 3}\n",
+            "  m_Name: Object\n",
+            "--- !u!114 &3\n",
+            "MonoBehaviour:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_GameObject: {fileID: 1}\n",
+            "  m_Script: {fileID: -1427037861, guid: 4ecd63eff847044b68db9453ce219299, type: 3}\n",
+            "  m_Name:\n",
+            "  fallbackStatus: 3\n",
+            "  completedSDKPipeline: 1\n",
+        ),
+    );
+    Ok(())
+}
+
+#[test]
+fn sort_non_ascii_name_test() -> anyhow::Result<()> {
+    // sort is by fileID only so names, including non-ASCII ones, don't affect the order
+    let game_object = |file_id: i64, name: &str| {
+        format!(
+            "--- !u!1 &{}\nGameObject:\n  m_ObjectHideFlags: 0\n  m_Name: {}\n",
+            file_id, name
+        )
+    };
+    let header = "%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n";
+    assert_eq!(
+        clean_document(
+            &[
+                header,
+                &game_object(3, "アバター"),
+                &game_object(1, "Ñandú"),
+                &game_object(2, "\"\\u3042 🎉\""),
+            ]
+            .concat()
+        )?,
+        [
+            header,
+            &game_object(1, "Ñandú"),
+            &game_object(2, "\"\\u3042 🎉\""),
+            &game_object(3, "アバター"),
+        ]
+        .concat(),
+    );
+    Ok(())
+}
+
+#[test]
+fn sort_test() -> anyhow::Result<()> {
+    let app = <App as clap::Parser>::parse_from(["clean"]);
+    let blob = "Ai8AAAAAATIAAABWAFIAQwAuAFUAZABvAG4ALgBDAG8AbQBtAG8AbgAu+/==".repeat(1000);
+    let udon_behaviour = |file_id: i64| {
+        format!(
+            concat!(
+                "--- !u!114 &{0}\n",
+                "MonoBehaviour:\n",
+                "  m_ObjectHideFlags: 0\n",
+                "  m_GameObject: {{fileID: 1}}\n",
+                "  m_Script: {{fileID: 11500000, guid: 45115577ef41a5b4ca741ed302693907, type: 3}}\n",
+                "  m_Name:\n",
+                "  serializedPublicVariablesBytesString: {1}{0}\n",
+                "  publicVariablesUnityEngineObjects: []\n",
+            ),
+            file_id, blob
+        )
+    };
+    let game_object = concat!(
+        "--- !u!1 &1\n",
+        "GameObject:\n",
+        "  m_ObjectHideFlags: 0\n",
+        "  m_Name: Object\n",
+    );
+    let header = "%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n";
+
+    let mut output = Vec::<u8>::new();
+    app.write_cleaned_yaml(
+        &[
+            header,
+            udon_behaviour(3).as_str(),
+            game_object,
+            udon_behaviour(2).as_str(),
+        ]
+        .concat(),
+        true,
+        &mut output,
+    )?;
+    // sections are only reordered
+    assert!(
+        String::from_utf8(output)?
+            == [
+                header,
+                game_object,
+                udon_behaviour(2).as_str(),
+                udon_behaviour(3).as_str()
+            ]
+            .concat()
+    );
+    Ok(())
+}
+
+#[test]
+fn prefab_variant_test() -> anyhow::Result<()> {
+    let app = <App as clap::Parser>::parse_from(["clean", "--remove-empty-game-objects"]);
+    let mut output = Vec::<u8>::new();
+    app.write_cleaned_yaml(
+        concat!(
+            "--- !u!1001 &100\n",
+            "PrefabInstance:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 2\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications:\n",
+            "    - target: {fileID: 11, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+            "      propertyPath: fallbackStatus\n",
+            "      value: 3\n",
+            "      objectReference: {fileID: 0}\n",
+            "    - target: {fileID: 11, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+            "      propertyPath: target\n",
+            "      value:\n",
+            "      objectReference: {fileID: 102}\n",
+            "    m_RemovedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+            "--- !u!4 &101 stripped\n",
+            "Transform:\n",
+            "  m_CorrespondingSourceObject: {fileID: 12, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+            "  m_PrefabInstance: {fileID: 100}\n",
+            "  m_PrefabAsset: {fileID: 0}\n",
+            "--- !u!4 &102 stripped\n",
+            "Transform:\n",
+            "  m_CorrespondingSourceObject: {fileID: 13, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+            "  m_PrefabInstance: {fileID: 100}\n",
+            "  m_PrefabAsset: {fileID: 0}\n",
+            "--- !u!4 &103 stripped\n",
+            "Transform:\n",
+            "  m_CorrespondingSourceObject: {fileID: 14, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+            "  m_PrefabInstance: {fileID: 100}\n",
+            "  m_PrefabAsset: {fileID: 0}\n",
+            "--- !u!114 &104 stripped\n",
+            "MonoBehaviour:\n",
+            "  m_CorrespondingSourceObject: {fileID: 11, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+            "  m_PrefabInstance: {fileID: 100}\n",
+            "  m_PrefabAsset: {fileID: 0}\n",
+            "--- !u!1 &200\n",
+            "GameObject:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 6\n",
+            "  m_Component:\n",
+            "  - component: {fileID: 201}\n",
+            "  m_Layer: 0\n",
+            "  m_Name: Added\n",
+            "--- !u!4 &201\n",
+            "Transform:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_GameObject: {fileID: 200}\n",
+            "  m_Children: []\n",
+            "  m_Father: {fileID: 101}\n",
+            "--- !u!1001 &300\n",
+            "PrefabInstance:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 2\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 103}\n",
+            "    m_Modifications: []\n",
+            "    m_RemovedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 1234567890abcdef1234567890abcdef, type: 3}\n",
+        ),
+        false,
+        &mut output,
+    )?;
+    assert_eq!(
+        String::from_utf8(output)?,
+        concat!(
+            "--- !u!1001 &100\n",
+            "PrefabInstance:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 2\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 0}\n",
+            "    m_Modifications:\n",
+            "    - target: {fileID: 11, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+            "      propertyPath: target\n",
+            "      value:\n",
+            "      objectReference: {fileID: 102}\n",
+            "    m_RemovedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+            "--- !u!4 &101 stripped\n",
+            "Transform:\n",
+            "  m_CorrespondingSourceObject: {fileID: 12, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+            "  m_PrefabInstance: {fileID: 100}\n",
+            "  m_PrefabAsset: {fileID: 0}\n",
+            "--- !u!4 &102 stripped\n",
+            "Transform:\n",
+            "  m_CorrespondingSourceObject: {fileID: 13, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+            "  m_PrefabInstance: {fileID: 100}\n",
+            "  m_PrefabAsset: {fileID: 0}\n",
+            "--- !u!4 &103 stripped\n",
+            "Transform:\n",
+            "  m_CorrespondingSourceObject: {fileID: 14, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+            "  m_PrefabInstance: {fileID: 100}\n",
+            "  m_PrefabAsset: {fileID: 0}\n",
+            "--- !u!1 &200\n",
+            "GameObject:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 6\n",
+            "  m_Component:\n",
+            "  - component: {fileID: 201}\n",
+            "  m_Layer: 0\n",
+            "  m_Name: Added\n",
+            "--- !u!4 &201\n",
+            "Transform:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  m_GameObject: {fileID: 200}\n",
+            "  m_Children: []\n",
+            "  m_Father: {fileID: 101}\n",
+            "--- !u!1001 &300\n",
+            "PrefabInstance:\n",
+            "  m_ObjectHideFlags: 0\n",
+            "  serializedVersion: 2\n",
+            "  m_Modification:\n",
+            "    m_TransformParent: {fileID: 103}\n",
+            "    m_Modifications: []\n",
+            "    m_RemovedComponents: []\n",
+            "  m_SourcePrefab: {fileID: 100100000, guid: 1234567890abcdef1234567890abcdef, type: 3}\n",
+        )
+    );
+    Ok(())
 }
 
 /// optimize yaml. remove unused stripped object
 fn optimize_yaml(sections: &mut [YamlSection]) {
-    for i in 0..sections.len() {
-        let sec = &mut sections[i];
-
-        if sec.parsed.is_stripped() {
-            let find = format!("{{fileID: {}}}", sec.parsed.file_id());
-            // find `{fileID: <file-id>}`
-
-            let mut found = false;
-            for j in 0..sections.len() {
-                if sections[j].filtered.contains(&find) {
-                    found = true;
-                    break;
+    if !sections.iter().any(|x| x.parsed.is_stripped()) {
+        return;
+    }
+
+    let mut references = HashSet::new();
+    for sec in sections.iter() {
+        collect_local_references(&sec.filtered, &mut references);
+    }
+
+    for sec in sections.iter_mut() {
+        if sec.parsed.is_stripped() && !references.contains(&sec.parsed.file_id()) {
+            sec.filtered = Cow::Borrowed("");
+        }
+    }
+}
+
+/// collects file ids of local references like `{fileID: <file-id>}`.
+/// references with guid or type key are references to other files so not collected.
+/// if the yaml cannot be scanned, the yaml is treated as referencing nothing.
+fn collect_local_references(yaml: &str, references: &mut HashSet<i64>) {
+    let mut found = Vec::new();
+    match scan_local_references(yaml, &mut found) {
+        Ok(()) => references.extend(found),
+        Err(e) => warn!(
+            "failed to scan references. the section is treated as referencing nothing: {}",
+            e
+        ),
+    }
+}
+
+/// this scans tokens so references with any spacing or line breaks are found.
+fn scan_local_references(yaml: &str, references: &mut Vec<i64>) -> Result<(), ScanError> {
+    let mut scanner = Scanner::new(yaml.chars());
+    // fileID and whether guid or type key is found for each flow mapping
+    let mut flow_mappings = Vec::<(Option<i64>, bool)>::new();
+    let mut in_key = false;
+    let mut key = String::new();
+
+    while let Some(Token(_, token)) = scanner.next_token()? {
+        match token {
+            FlowMappingStart => flow_mappings.push((None, false)),
+            FlowMappingEnd => {
+                if let Some((Some(file_id), false)) = flow_mappings.pop() {
+                    references.push(file_id);
+                }
+                key.clear();
+            }
+            Key => in_key = true,
+            Value => in_key = false,
+            Scalar(_, value) if in_key => {
+                // the key is enough even if the value is empty like `guid: `
+                if let Some((_, external)) = flow_mappings.last_mut() {
+                    if value == "guid" || value == "type" {
+                        *external = true;
+                    }
                 }
+                key = value;
             }
-            if !found {
-                sections[i].filtered = Cow::Borrowed("");
+            Scalar(_, value) => {
+                if let Some((file_id, _)) = flow_mappings.last_mut() {
+                    if key == "fileID" {
+                        *file_id = value.parse().ok();
+                    }
+                }
+                key.clear();
             }
+            _ => {}
         }
     }
+    Ok(())
 }
 
 #[test]
@@ -158,6 +1442,105 @@ fn optimize_yaml_test() {
         ]
     );
 
+    // references with other spacing
+    for reference in [
+        "{fileID:  484105423}",
+        "{ fileID: 484105423 }",
+        "\n    {fileID:\n      484105423}",
+    ] {
+        let body = format!("MonoBehaviour:\n  script: {}\n", reference);
+        let mut sections = [
+            YamlSection {
+                heading: "--- !u!114 &484105423 stripped",
+                parsed: ParsedHeadingLine::new(484105423, true),
+                filtered: Cow::Borrowed("MonoBehaviour:\n"),
+            },
+            YamlSection {
+                heading: "--- !u!114 &2087762956",
+                parsed: ParsedHeadingLine::new(2087762956, false),
+                filtered: Cow::Borrowed(&body),
+            },
+        ];
+        optimize_yaml(&mut sections);
+        assert_eq!(sections[0].filtered, "MonoBehaviour:\n", "{}", reference);
+    }
+
+    // references to other file are not references to stripped object
+    test!(
+        [
+            YamlSection {
+                heading: "--- !u!114 &484105423 stripped",
+                parsed: ParsedHeadingLine::new(484105423, true),
+                filtered: Cow::Borrowed(""),
+            },
+            YamlSection {
+                heading: "--- !u!114 &2087762956",
+                parsed: ParsedHeadingLine::new(2087762956, false),
+                filtered: Cow::Borrowed(
+                    "MonoBehaviour:\n  script: {fileID: 484105423, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n"
+                ),
+            }
+        ],
+        [
+            YamlSection {
+                heading: "--- !u!114 &484105423 stripped",
+                parsed: ParsedHeadingLine::new(484105423, true),
+                filtered: Cow::Borrowed("MonoBehaviour:\n"),
+            },
+            YamlSection {
+                heading: "--- !u!114 &2087762956",
+                parsed: ParsedHeadingLine::new(2087762956, false),
+                filtered: Cow::Borrowed(
+                    "MonoBehaviour:\n  script: {fileID: 484105423, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n"
+                ),
+            }
+        ]
+    );
+
+    // guid-bearing references sharing the id with the stripped object in other forms
+    for (body, kept) in [
+        (
+            "MonoBehaviour:\n  script: {guid: 26db88bf250934ccca835bd9318c0eeb, fileID: 484105423, type: 3}\n",
+            false,
+        ),
+        (
+            "MonoBehaviour:\n  script: {fileID: 484105423, guid: 26db88bf250934ccca835bd9318c0eeb,\n    type: 3}\n",
+            false,
+        ),
+        // a reference with guid or type key without guid value is not a local reference
+        ("MonoBehaviour:\n  script: {fileID: 484105423, type: 3}\n", false),
+        ("MonoBehaviour:\n  script: {fileID: 484105423, guid: , type: 3}\n", false),
+        ("MonoBehaviour:\n  script: {fileID: 484105423, guid: }\n", false),
+        // a section which cannot be scanned is treated as referencing nothing
+        (
+            "MonoBehaviour:\n  target: {fileID: 484105423}\n  m_Name: \"unterminated\n",
+            false,
+        ),
+        (
+            concat!(
+                "MonoBehaviour:\n",
+                "  script: {fileID: 484105423, guid: 26db88bf250934ccca835bd9318c0eeb, type: 3}\n",
+                "  target: {fileID: 484105423}\n",
+            ),
+            true,
+        ),
+    ] {
+        let mut sections = [
+            YamlSection {
+                heading: "--- !u!114 &484105423 stripped",
+                parsed: ParsedHeadingLine::new(484105423, true),
+                filtered: Cow::Borrowed("MonoBehaviour:\n"),
+            },
+            YamlSection {
+                heading: "--- !u!114 &2087762956",
+                parsed: ParsedHeadingLine::new(2087762956, false),
+                filtered: Cow::Borrowed(body),
+            },
+        ];
+        optimize_yaml(&mut sections);
+        assert_eq!(sections[0].filtered.is_empty(), !kept, "{}", body);
+    }
+
     // remove that if no reference found
     test!(
         [
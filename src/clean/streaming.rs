@@ -0,0 +1,293 @@
+//! cleaning yaml without reading whole file into memory.
+//!
+//! sections are written in the original order as soon as no following section can change them.
+//! GameObjects wait until all of their components are read because components removed by
+//! the filter are removed from `m_Component` and stripped objects wait until the end of the file
+//! because they are removed if nothing references them.
+//! sorting and removing empty GameObjects need whole file so they are not supported.
+//! sections after a stripped object are also kept until the end of the file so this doesn't
+//! always reduce memory usage. this is enabled only with `--streaming`.
+
+use super::filter;
+use super::{collect_local_references, strip_stamp, warn_mixed_line_endings, App, YamlSection};
+use crate::yaml::{LineEndings, ParsedHeadingLine, YamlSeparated};
+use log::trace;
+use std::borrow::Cow;
+use std::collections::{HashSet, VecDeque};
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+pub(super) fn write_cleaned_yaml(
+    app: &App,
+    input: impl BufRead,
+    output: &mut impl Write,
+) -> anyhow::Result<()> {
+    let config = app.filter_config();
+    let mut reader = SectionReader::new(input);
+    let mut state = State::default();
+    let mut line_endings = LineEndings::default();
+
+    let mut current = reader.next_section()?;
+    while let Some(section) = current {
+        // the last section is needed to be known to strip stamp
+        current = reader.next_section()?;
+        let section = String::from_utf8(section)?;
+        let section = if current.is_none() {
+            strip_stamp(&section)
+        } else {
+            &section
+        };
+        if !line_endings.is_mixed() {
+            line_endings.add(section);
+            if line_endings.is_mixed() {
+                warn_mixed_line_endings(app.file.as_deref());
+            }
+        }
+
+        for (heading, body) in YamlSeparated::new(section) {
+            if heading.is_empty() {
+                // heading element without separator like `%YAML` and `%TAG`
+                output.write_all(body.as_bytes())?;
+                continue;
+            }
+            trace!("start: {}", heading);
+            let mut sections = [YamlSection {
+                heading,
+                parsed: ParsedHeadingLine::from_str(heading)?,
+                filtered: body.into(),
+            }];
+            filter::main::filter(&mut sections, &config)?;
+            let [filtered] = sections;
+            state.push(filtered)?;
+        }
+        state.flush(app, false, output)?;
+    }
+    state.flush(app, true, output)?;
+
+    Ok(())
+}
+
+/// reads yaml section by section. each section starts with `---` line.
+struct SectionReader<R> {
+    input: R,
+    /// the `---` line read at the end of the previous section
+    next_heading: Vec<u8>,
+}
+
+impl<R: BufRead> SectionReader<R> {
+    fn new(input: R) -> Self {
+        Self {
+            input,
+            next_heading: Vec::new(),
+        }
+    }
+
+    fn next_section(&mut self) -> std::io::Result<Option<Vec<u8>>> {
+        let mut section = std::mem::take(&mut self.next_heading);
+        loop {
+            let mut line = Vec::new();
+            if self.input.read_until(b'\n', &mut line)? == 0 {
+                break;
+            }
+            if line.starts_with(b"---") && !section.is_empty() {
+                self.next_heading = line;
+                break;
+            }
+            section.append(&mut line);
+        }
+        Ok(if section.is_empty() {
+            None
+        } else {
+            Some(section)
+        })
+    }
+}
+
+struct PendingSection {
+    heading: String,
+    parsed: ParsedHeadingLine,
+    filtered: String,
+    /// local file ids of components if this is a GameObject
+    components: Option<Vec<i64>>,
+}
+
+#[derive(Default)]
+struct State {
+    pending: VecDeque<PendingSection>,
+    /// file ids of all sections read
+    read: HashSet<i64>,
+    /// file ids of sections removed by the filter
+    removed: HashSet<i64>,
+    /// file ids referenced from any section
+    references: HashSet<i64>,
+}
+
+impl State {
+    fn push(&mut self, section: YamlSection) -> anyhow::Result<()> {
+        let file_id = section.parsed.file_id();
+        self.read.insert(file_id);
+        if section.filtered.is_empty() {
+            self.removed.insert(file_id);
+        }
+        collect_local_references(&section.filtered, &mut self.references);
+        let components = if section.filtered.is_empty() || !section.parsed.may_be_game_object() {
+            None
+        } else {
+            filter::remove_empty_game_objects::game_object_components(&section.filtered)?.map(
+                |components| {
+                    components
+                        .into_iter()
+                        .filter(|x| x.is_local())
+                        .map(|x| x.file_id)
+                        .collect()
+                },
+            )
+        };
+        self.pending.push_back(PendingSection {
+            heading: section.heading.to_owned(),
+            parsed: section.parsed,
+            filtered: section.filtered.into_owned(),
+            components,
+        });
+        Ok(())
+    }
+
+    /// writes sections which will not be changed by following sections.
+    /// if `eof` is true, all sections are written.
+    fn flush(&mut self, app: &App, eof: bool, output: &mut impl Write) -> anyhow::Result<()> {
+        while let Some(section) = self.pending.front() {
+            let ready = eof
+                || !section.parsed.is_stripped()
+                    && section
+                        .components
+                        .iter()
+                        .flatten()
+                        .all(|id| self.read.contains(id));
+            if !ready {
+                break;
+            }
+            let section = self.pending.pop_front().unwrap();
+            self.write_section(app, section, output)?;
+        }
+        Ok(())
+    }
+
+    fn write_section(
+        &self,
+        app: &App,
+        section: PendingSection,
+        output: &mut impl Write,
+    ) -> anyhow::Result<()> {
+        let mut filtered = Cow::Borrowed(section.filtered.as_str());
+        // same as optimize_yaml
+        if section.parsed.is_stripped() && !self.references.contains(&section.parsed.file_id()) {
+            filtered = Cow::Borrowed("");
+        }
+        // same as remove_components
+        if section.components.is_some() && !filtered.is_empty() {
+            filtered = filter::remove_components::filter_yaml(&section.filtered, |id| {
+                self.removed.contains(&id)
+            })?;
+        }
+
+        if !filtered.is_empty() {
+            write!(output, "{}{}", section.heading, filtered)?;
+        } else if app.keep_empty_sections {
+            write!(output, "{}", section.heading)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn streaming_test() -> anyhow::Result<()> {
+    let compare = |args: &[&str], yaml: &str| -> anyhow::Result<()> {
+        let app = <App as clap::Parser>::parse_from(args);
+        let mut buffered = Vec::<u8>::new();
+        app.write_cleaned_yaml(strip_stamp(yaml), false, &mut buffered)?;
+        let mut streaming = Vec::<u8>::new();
+        write_cleaned_yaml(&app, yaml.as_bytes(), &mut streaming)?;
+        assert_eq!(
+            String::from_utf8(streaming)?,
+            String::from_utf8(buffered)?,
+            "{}",
+            yaml
+        );
+        Ok(())
+    };
+
+    for fixture in [
+        include_str!("../../tests/fixtures/avatar.prefab"),
+        // stripped objects, PrefabInstance and removed components
+        include_str!("../../tests/fixtures/variant.prefab"),
+    ] {
+        compare(&["clean"], fixture)?;
+        compare(&["clean", "--keep-empty-sections"], fixture)?;
+        compare(&["clean"], &fixture.replace('\n', "\r\n"))?;
+    }
+
+    // components are removed after GameObject and stripped object is not referenced
+    let yaml = concat!(
+        "%YAML 1.1\n",
+        "%TAG !u! tag:unity3d.com,2011:\n",
+        "--- !u!1 &1\n",
+        "GameObject:\n",
+        "  m_ObjectHideFlags: 0\n",
+        "  serializedVersion: 6\n",
+        "  m_Component:\n",
+        "  - component: {fileID: 2}\n",
+        "  - component: {fileID: 3}\n",
+        "  m_Name: Object\n",
+        "--- !u!1 &5 stripped\n",
+        "GameObject:\n",
+        "  m_CorrespondingSourceObject: {fileID: 100, guid: 0123456789abcdef0123456789abcdef, type: 3}\n",
+        "  m_PrefabInstance: {fileID: 6}\n",
+        "--- !u!4 &2\n",
+        "Transform:\n",
+        "  m_ObjectHideFlags: 0\n",
+        "  m_GameObject: {fileID: 1}\n",
+        "  m_Children: []\n",
+        "  m_Father: {fileID: 0}\n",
+        "--- !u!114 &3\n",
+        "MonoBehaviour:\n",
+        "  m_ObjectHideFlags: 0\n",
+        "  m_GameObject: {fileID: 1}\n",
+        "  m_Script: {fileID: 229740497, guid: 4ecd63eff847044b68db9453ce219299, type: 3}\n",
+        "--- !u!1 &4 stripped\n",
+        "GameObject:\n",
+        "  m_CorrespondingSourceObject: {fileID: 101, guid: 0123456789abcdef0123456789abcdef, type: 3}\n",
+        "  m_PrefabInstance: {fileID: 6}\n",
+        "--- !u!114 &7\n",
+        "MonoBehaviour:\n",
+        "  m_ObjectHideFlags: 0\n",
+        "  m_GameObject: {fileID: 4}\n",
+        "  m_Enabled: 1\n",
+    );
+    compare(&["clean"], yaml)?;
+    compare(&["clean", "--keep-empty-sections"], yaml)?;
+
+    // stamp at the end
+    compare(
+        &["clean"],
+        &format!("{}{}0.0.0\n", yaml, super::STAMP_PREFIX),
+    )?;
+
+    // mixed line endings
+    compare(&["clean"], &yaml.replacen('\n', "\r\n", 5))?;
+
+    // --streaming gives the same result as the default
+    let fixture = include_str!("../../tests/fixtures/variant.prefab");
+    let mut buffered = Vec::<u8>::new();
+    <App as clap::Parser>::parse_from(["clean"]).clean(&mut fixture.as_bytes(), &mut buffered)?;
+    let mut streaming = Vec::<u8>::new();
+    <App as clap::Parser>::parse_from(["clean", "--streaming"])
+        .clean(&mut fixture.as_bytes(), &mut streaming)?;
+    assert_eq!(String::from_utf8(streaming)?, String::from_utf8(buffered)?);
+
+    // headerless yaml and section without body
+    compare(
+        &["clean"],
+        "--- !u!1 &1\nGameObject:\n  m_Name: Object\n--- !u!1 &2\n",
+    )?;
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use serde::Deserialize;
+use std::path::Path;
+
+/// the name of config file at the repository root
+pub(crate) const CONFIG_FILE_NAME: &str = ".git-vrc.toml";
+
+/// configuration committed to the repository as `.git-vrc.toml`.
+/// git attributes still override the values in this file.
+#[derive(Deserialize, Default, Debug, PartialEq)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct Config {
+    pub(crate) clean: CleanConfig,
+}
+
+/// `[clean]` section. each value is same as the option of `git vrc clean`
+#[derive(Deserialize, Default, Debug, PartialEq)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct CleanConfig {
+    pub(crate) sort: Option<bool>,
+    pub(crate) remove_empty_game_objects: Option<bool>,
+    pub(crate) indirect_specular_color_tolerance: Option<f32>,
+    pub(crate) normalize_editor_class_identifier: Option<bool>,
+    pub(crate) normalize_view_position: Option<bool>,
+    pub(crate) normalize_negative_zero: Option<bool>,
+    pub(crate) trim_name: Option<bool>,
+    pub(crate) remove_orphan_array_size: Option<bool>,
+    pub(crate) normalize_default_animator_controller: Option<bool>,
+    pub(crate) normalize_auto_colliders: Option<bool>,
+    pub(crate) normalize_ambient_probe: Option<bool>,
+    pub(crate) sort_removed_components: Option<bool>,
+    pub(crate) normalize_station_null_references: Option<bool>,
+}
+
+impl Config {
+    /// loads config file at the repository root.
+    /// returns default config if not in repository or the file doesn't exist.
+    pub(crate) fn load(root: Option<&Path>) -> anyhow::Result<Config> {
+        match root {
+            Some(root) => Self::load_from(&root.join(CONFIG_FILE_NAME)),
+            None => Ok(Config::default()),
+        }
+    }
+
+    fn load_from(path: &Path) -> anyhow::Result<Config> {
+        match std::fs::read_to_string(path) {
+            Ok(str) => Self::parse(&str),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub(crate) fn parse(str: &str) -> anyhow::Result<Config> {
+        Ok(toml::from_str(str)?)
+    }
+}
+
+#[test]
+fn parse_test() -> anyhow::Result<()> {
+    assert_eq!(Config::parse("")?, Config::default());
+    assert_eq!(
+        Config::parse(concat!(
+            "[clean]\n",
+            "sort = true\n",
+            "normalize-view-position = true\n",
+            "indirect-specular-color-tolerance = 0.001\n",
+        ))?,
+        Config {
+            clean: CleanConfig {
+                sort: Some(true),
+                remove_empty_game_objects: None,
+                indirect_specular_color_tolerance: Some(0.001),
+                normalize_editor_class_identifier: None,
+                normalize_view_position: Some(true),
+                normalize_negative_zero: None,
+                trim_name: None,
+                remove_orphan_array_size: None,
+                normalize_default_animator_controller: None,
+                normalize_auto_colliders: None,
+                normalize_ambient_probe: None,
+                sort_removed_components: None,
+                normalize_station_null_references: None,
+            }
+        }
+    );
+    assert!(Config::parse("[clean]\nunknown-rule = true\n").is_err());
+    Ok(())
+}
+
+#[test]
+fn load_not_found_test() -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join(format!(
+        "git-vrc-test-config-not-found-{}",
+        std::process::id()
+    ));
+    assert_eq!(Config::load_from(&path)?, Config::default());
+    Ok(())
+}
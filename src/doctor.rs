@@ -0,0 +1,138 @@
+use crate::config::{Config, CONFIG_FILE_NAME};
+use crate::install::{attributes_installed_at, config_installed_with, GitConfigOptions};
+use anyhow::{bail, Result};
+use clap::Parser;
+use log::{info, warn};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+#[derive(Parser)]
+/// Diagnoses installation of git-vrc and suggests how to fix problems
+pub(crate) struct App {
+    /// path to .gitattributes configured with `git vrc install --attributes-path`.
+    /// relative path is resolved from the repository root.
+    #[clap(long)]
+    attributes_path: Option<PathBuf>,
+}
+
+/// a problem found by doctor
+#[derive(Debug, Eq, PartialEq)]
+enum Problem {
+    GitNotFound,
+    ConfigNotInstalled,
+    AttributesNotInstalled,
+    InvalidConfigFile,
+}
+
+impl Problem {
+    fn message(&self) -> &'static str {
+        match self {
+            Problem::GitNotFound => "git is not found. install git and add it to PATH",
+            Problem::ConfigNotInstalled => {
+                "git config for git-vrc is not installed. run `git vrc install --config --global`"
+            }
+            Problem::AttributesNotInstalled => {
+                "git attributes for git-vrc is not installed. run `git vrc install --attributes`"
+            }
+            Problem::InvalidConfigFile => {
+                "failed to load .git-vrc.toml so clean uses the default config. fix the file"
+            }
+        }
+    }
+}
+
+impl App {
+    pub(crate) fn run(self) -> Result<()> {
+        let problems = diagnose(|| Command::new("git"), self.attributes_path.as_deref());
+        if problems.is_empty() {
+            info!("no problems found");
+            return Ok(());
+        }
+        for problem in &problems {
+            warn!("{}", problem.message());
+        }
+        bail!("{} problem(s) found", problems.len())
+    }
+}
+
+/// runs all diagnostics with git commands created by `git`.
+/// this uses the same detection as `git vrc install --check`.
+fn diagnose(git: impl Fn() -> Command, attributes_path: Option<&Path>) -> Vec<Problem> {
+    let found = git()
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false);
+    if !found {
+        return vec![Problem::GitNotFound];
+    }
+
+    let mut problems = Vec::new();
+
+    if !config_installed_with(git(), &GitConfigOptions::default()).unwrap_or(false) {
+        problems.push(Problem::ConfigNotInstalled);
+    }
+
+    // attributes are configured per repository
+    if let Some(root) = crate::git::repo_root_with(git()) {
+        if !attributes_installed_at(&git, &root, attributes_path, false).unwrap_or(false) {
+            problems.push(Problem::AttributesNotInstalled);
+        }
+        if let Err(e) = Config::load(Some(&root)) {
+            warn!("{}: {}", CONFIG_FILE_NAME, e);
+            problems.push(Problem::InvalidConfigFile);
+        }
+    }
+
+    problems
+}
+
+#[test]
+fn diagnose_test() -> std::io::Result<()> {
+    let dir = std::env::temp_dir().join(format!("git-vrc-test-doctor-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    let git = || {
+        let mut command = Command::new("git");
+        command
+            .current_dir(&dir)
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .env("GIT_CONFIG_GLOBAL", dir.join("global-config"));
+        command
+    };
+    assert!(git().args(["init", "-q"]).status()?.success());
+    let sub_project = Path::new("SubProject/.gitattributes");
+
+    let not_installed = diagnose(git, None);
+
+    std::fs::create_dir_all(dir.join("SubProject"))?;
+    std::fs::write(
+        dir.join(sub_project),
+        "*.asset filter=vrc\n*.prefab filter=vrc\n*.unity filter=vrc\n",
+    )?;
+    let config = git()
+        .args(["config", "filter.vrc.clean", "git vrc clean --file %f"])
+        .status()?;
+    assert!(config.success());
+    let sub_project_installed = diagnose(git, Some(sub_project));
+    let root_not_installed = diagnose(git, None);
+    std::fs::write(dir.join(CONFIG_FILE_NAME), "[clean]\nunknown-rule = true\n")?;
+    let invalid_config = diagnose(git, Some(sub_project));
+
+    let not_found = diagnose(|| Command::new("git-vrc-test-not-found"), None);
+    std::fs::remove_dir_all(&dir)?;
+
+    assert_eq!(
+        not_installed,
+        vec![Problem::ConfigNotInstalled, Problem::AttributesNotInstalled]
+    );
+    assert_eq!(sub_project_installed, vec![]);
+    // .gitattributes of SubProject doesn't affect files in the root
+    assert_eq!(root_not_installed, vec![Problem::AttributesNotInstalled]);
+    assert_eq!(invalid_config, vec![Problem::InvalidConfigFile]);
+    assert_eq!(not_found, vec![Problem::GitNotFound]);
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use crate::yaml::YamlSeparated;
+use clap::Parser;
+use std::fs::File;
+use std::io;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use yaml_rust::scanner::*;
+
+#[derive(Parser)]
+/// Dumps yaml tokens of each section for debugging the parser.
+pub(crate) struct App {
+    /// read from this file instead of stdin
+    #[clap(long = "input-file")]
+    input_file: Option<PathBuf>,
+}
+
+impl App {
+    pub(crate) fn run(self) -> anyhow::Result<()> {
+        let mut input: Box<dyn Read> = match &self.input_file {
+            Some(path) => Box::new(File::open(path)?),
+            None => Box::new(io::stdin()),
+        };
+        let mut yaml = String::new();
+        input.read_to_string(&mut yaml)?;
+
+        let stdout = io::stdout();
+        let mut stdout = stdout.lock();
+        dump_tokens(&yaml, &mut stdout)?;
+        stdout.flush()?;
+        Ok(())
+    }
+}
+
+fn dump_tokens(yaml: &str, output: &mut impl Write) -> anyhow::Result<()> {
+    for (heading, body) in YamlSeparated::new(yaml) {
+        writeln!(output, "section: {:?}", heading)?;
+        // heading element like `%YAML` is not parsed by this tool
+        if heading.is_empty() {
+            continue;
+        }
+        let mut scanner = Scanner::new(body.chars());
+        loop {
+            match scanner.next_token() {
+                Ok(Some(Token(marker, token))) => writeln!(output, "  {:?} {:?}", marker, token)?,
+                Ok(None) => break,
+                Err(e) => {
+                    writeln!(output, "  error: {}", e)?;
+                    break;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn dump_tokens_test() -> anyhow::Result<()> {
+    let mut output = Vec::<u8>::new();
+    dump_tokens(
+        concat!(
+            "%YAML 1.1\n",
+            "%TAG !u! tag:unity3d.com,2011:\n",
+            "--- !u!1 &1\n",
+            "GameObject:\n",
+            "  m_Name: Object\n",
+        ),
+        &mut output,
+    )?;
+    let output = String::from_utf8(output)?;
+    assert!(output.contains("section: \"--- !u!1 &1\\n\""));
+    assert!(output.contains("BlockMappingStart"));
+    assert!(output.contains("Scalar(Plain, \"m_Name\")"));
+    Ok(())
+}
@@ -5,8 +5,13 @@ use std::path::PathBuf;
 use std::process::{Command, Stdio};
 
 fn get_path_command(args: &[impl AsRef<OsStr>]) -> Option<PathBuf> {
-    let mut result = Command::new("git")
-        .args(args)
+    let mut command = Command::new("git");
+    command.args(args);
+    get_path(command)
+}
+
+fn get_path(mut command: Command) -> Option<PathBuf> {
+    let mut result = command
         .stdout(Stdio::piped())
         .stderr(Stdio::null())
         .stdin(Stdio::null())
@@ -24,14 +29,23 @@ fn get_path_command(args: &[impl AsRef<OsStr>]) -> Option<PathBuf> {
 }
 
 pub(crate) fn repo_root() -> Option<PathBuf> {
-    get_path_command(&["rev-parse", "--show-toplevel"])
+    repo_root_with(Command::new("git"))
+}
+
+pub(crate) fn repo_root_with(mut command: Command) -> Option<PathBuf> {
+    command.args(["rev-parse", "--show-toplevel"]);
+    get_path(command)
+}
+
+pub(crate) fn git_dir() -> Option<PathBuf> {
+    get_path_command(&["rev-parse", "--git-dir"])
 }
 
-pub(crate) fn check_attr(
+pub(crate) fn check_attr_with(
+    mut command: Command,
     attrs: &[impl AsRef<OsStr>],
     targets: &[impl AsRef<OsStr>],
 ) -> io::Result<GitCheckAttrResult> {
-    let mut command = Command::new("git");
     command.arg("check-attr").arg("-z");
     command.args(attrs).arg("--").args(targets);
     command.stdout(Stdio::piped());
@@ -69,6 +83,25 @@ pub(crate) struct GitCheckAttrResult {
     index: usize,
 }
 
+impl GitCheckAttrResult {
+    /// finds next '\0' and returns the index of it.
+    /// if not found, which means the output is truncated, this ends iteration.
+    fn find_separator(&mut self) -> Option<usize> {
+        match self.str[self.index..].find('\0') {
+            Some(found) => {
+                let sep = self.index + found;
+                self.index = sep + 1;
+                Some(sep)
+            }
+            None => {
+                debug!("truncated check-attr output: {:?}", &self.str[self.index..]);
+                self.index = self.str.len();
+                None
+            }
+        }
+    }
+}
+
 impl Iterator for GitCheckAttrResult {
     type Item = (String, String, String);
 
@@ -79,19 +112,10 @@ impl Iterator for GitCheckAttrResult {
         let begin = self.index;
         debug!("find since {:?}", self.index);
 
-        self.index += self.str[self.index..].find('\0').expect("no \\0 found");
-        let first_sep = self.index;
-        self.index += 1;
-
-        self.index += self.str[self.index..].find('\0').expect("no \\0 found");
-        let second_sep = self.index;
-        self.index += 1;
+        let first_sep = self.find_separator()?;
+        let second_sep = self.find_separator()?;
+        let third_sep = self.find_separator()?;
 
-        self.index += self.str[self.index..].find('\0').expect("no \\0 found");
-        let third_sep = self.index;
-        self.index += 1;
-
-        self.index = third_sep;
         unsafe {
             Some((
                 self.str.get_unchecked(begin..first_sep).to_string(),
@@ -105,3 +129,82 @@ impl Iterator for GitCheckAttrResult {
         }
     }
 }
+
+#[test]
+fn git_check_attr_result() {
+    assert_eq!(
+        GitCheckAttrResult {
+            str: concat!(
+                "Assets/My Scene.unity\0filter\0vrc\0",
+                "Assets/Prefabs/My Prefab (1).prefab\0unity-sort\0unspecified\0",
+            )
+            .to_owned(),
+            index: 0,
+        }
+        .collect::<Vec<_>>(),
+        vec![
+            (
+                "Assets/My Scene.unity".to_owned(),
+                "filter".to_owned(),
+                "vrc".to_owned()
+            ),
+            (
+                "Assets/Prefabs/My Prefab (1).prefab".to_owned(),
+                "unity-sort".to_owned(),
+                "unspecified".to_owned()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn git_check_attr_result_truncated() {
+    let mut result = GitCheckAttrResult {
+        str: concat!(
+            "Assets/My Scene.unity\0filter\0vrc\0",
+            "Assets/Prefabs/My Prefab.prefab\0unity-sort\0unspec",
+        )
+        .to_owned(),
+        index: 0,
+    };
+    assert_eq!(
+        result.next(),
+        Some((
+            "Assets/My Scene.unity".to_owned(),
+            "filter".to_owned(),
+            "vrc".to_owned()
+        ))
+    );
+    assert_eq!(result.next(), None);
+    assert_eq!(result.next(), None);
+}
+
+#[test]
+fn check_attr_per_file() -> io::Result<()> {
+    let dir = std::env::temp_dir().join(format!(
+        "git-vrc-test-check-attr-per-file-{}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir)?;
+    let status = Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(&dir)
+        .status()?;
+    assert!(status.success());
+    std::fs::write(
+        dir.join(".gitattributes"),
+        "*.asset unity-sort\nCurated.asset -unity-sort\n",
+    )?;
+
+    let mut command = Command::new("git");
+    command.current_dir(&dir);
+    let result = check_attr_with(command, &["unity-sort"], &["Other.asset", "Curated.asset"]);
+    std::fs::remove_dir_all(&dir)?;
+
+    // the attribute for specific file wins over broader one
+    assert_eq!(
+        result?.map(|(_, _, value)| value).collect::<Vec<_>>(),
+        vec!["set".to_owned(), "unset".to_owned()]
+    );
+    Ok(())
+}
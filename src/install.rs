@@ -5,7 +5,7 @@ use std::collections::HashSet;
 use std::fs::OpenOptions;
 use std::io;
 use std::io::{Read, Seek, SeekFrom, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
 #[derive(Parser)]
@@ -34,9 +34,23 @@ pub(crate) struct App {
     /// configure .gitattributes
     #[clap(long)]
     attributes: bool,
+    /// path to .gitattributes to configure instead of .gitattributes at the repository root.
+    /// relative path is resolved from the repository root. implies --attributes
+    #[clap(long)]
+    attributes_path: Option<PathBuf>,
+    /// configure $GIT_DIR/info/attributes, which is not committed
+    #[clap(long)]
+    info_attributes: bool,
+    /// check if git-vrc is installed without making changes. exits with non-zero if not.
+    #[clap(long)]
+    check: bool,
+    /// add merge=union to the attributes of unity files to reduce merge conflicts.
+    /// union merge may create invalid YAML so check merged files.
+    #[clap(long)]
+    merge_union: bool,
 }
 
-#[derive(Parser)]
+#[derive(Parser, Default)]
 #[clap(group(
     ArgGroup::new("git-config")
         .required(false)
@@ -63,8 +77,7 @@ impl GitConfigOptions {
         self.system || self.global || self.local || self.worktree
     }
 
-    pub(crate) fn exists(&self, key: &str, anywhere: bool) -> io::Result<bool> {
-        let mut command = Command::new("git");
+    fn exists_with(&self, mut command: Command, key: &str, anywhere: bool) -> io::Result<bool> {
         command.stdin(Stdio::null()).stdout(Stdio::null());
         command.arg("config");
         if !anywhere {
@@ -74,8 +87,38 @@ impl GitConfigOptions {
         Ok(command.status()?.success())
     }
 
+    /// use --system if no git config target is specified
+    pub(crate) fn default_to_system(&mut self) {
+        if !self.set_any() {
+            self.system = true;
+        }
+    }
+
+    pub(crate) fn unset(&self, key: &str) -> io::Result<()> {
+        self.unset_with(Command::new("git"), key)
+    }
+
+    fn unset_with(&self, mut command: Command, key: &str) -> io::Result<()> {
+        command.stdin(Stdio::null()).stdout(Stdio::null());
+        command.arg("config");
+        self.options(&mut command);
+        command.arg("--unset").arg("--").arg(key);
+        let status = command.status()?;
+        // 5 means the key doesn't exist, which is already unset
+        if !status.success() && status.code() != Some(5) {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "git config command returns non-zero value",
+            ));
+        }
+        Ok(())
+    }
+
     pub(crate) fn set(&self, key: &str, value: &str) -> io::Result<()> {
-        let mut command = Command::new("git");
+        self.set_with(Command::new("git"), key, value)
+    }
+
+    fn set_with(&self, mut command: Command, key: &str, value: &str) -> io::Result<()> {
         command.stdin(Stdio::null()).stdout(Stdio::null());
         command.arg("config");
         self.options(&mut command);
@@ -108,10 +151,18 @@ impl GitConfigOptions {
 
 impl App {
     fn default_target(&self) -> bool {
-        !self.config && !self.attributes
+        !self.config && !self.attributes && !self.info_attributes
     }
 
     pub(crate) fn run(mut self) -> Result<()> {
+        if self.check {
+            return self.check_installed_with(|| Command::new("git"));
+        }
+
+        if self.attributes_path.is_some() {
+            self.attributes = true;
+        }
+
         let config_always;
         let attributes_always;
         if self.default_target() {
@@ -128,11 +179,18 @@ impl App {
             bail!("git config options is not valid without --config")
         }
 
-        if !self.git_config_options.set_any() {
-            // system by default
-            self.git_config_options.system = true;
+        if self.merge_union {
+            if !self.attributes && !self.info_attributes {
+                bail!("--merge-union is not valid without --attributes or --info-attributes")
+            }
+            warn!(
+                "merge=union keeps lines of both sides and may create invalid YAML. \
+                please check merged unity files."
+            );
         }
 
+        self.git_config_options.default_to_system();
+
         if self.config {
             self.configure_config(config_always)?;
         }
@@ -141,16 +199,40 @@ impl App {
             self.configure_attributes(attributes_always)?;
         }
 
+        if self.info_attributes {
+            self.configure_info_attributes()?;
+        }
+
+        Ok(())
+    }
+
+    fn check_installed_with(&self, git: impl Fn() -> Command) -> Result<()> {
+        let config = config_installed_with(git(), &self.git_config_options)?;
+        if !config {
+            warn!("git config for git-vrc is not installed");
+        }
+        // attributes are configured per repository
+        let attributes = match crate::git::repo_root_with(git()) {
+            None => true,
+            Some(root) => attributes_installed_at(
+                git,
+                &root,
+                self.attributes_path.as_deref(),
+                self.merge_union,
+            )?,
+        };
+        if !attributes {
+            warn!("git attributes for git-vrc is not installed");
+        }
+        if !config || !attributes {
+            bail!("git-vrc is not installed. run `git vrc install`");
+        }
         Ok(())
     }
 
     fn configure_config(&self, always: bool) -> Result<()> {
         if !always {
-            if self
-                .git_config_options
-                .exists("filter.vrc.clean", true)
-                .context("git config to check exists")?
-            {
+            if config_installed(&self.git_config_options)? {
                 // if there's filter.vrc.clean, there's no need to
                 return Ok(());
             }
@@ -168,57 +250,194 @@ impl App {
     }
 
     fn configure_attributes(&self, always: bool) -> Result<()> {
+        let root = crate::git::repo_root();
         if !always {
             // if CWD is not git repo, this doesn't run
-            if crate::git::repo_root().is_none() {
-                return Ok(());
-            }
+            let root = match &root {
+                Some(root) => root,
+                None => return Ok(()),
+            };
             // if all required config are set, nothing to do
-            if crate::git::check_attr(
-                &["filter", "diff", "merge"],
-                &["*.asset", "*.prefab", "*.unity"],
-            )?
-            .all(|(_file, _kind, value)| value == "vrc")
-            {
+            if attributes_installed_at(
+                || Command::new("git"),
+                root,
+                self.attributes_path.as_deref(),
+                self.merge_union,
+            )? {
                 return Ok(());
             }
         }
-        let file_path = Path::new(".gitattributes");
-
-        // try create new .gitattributes.
-        if let Ok(mut file) = OpenOptions::new()
-            .create_new(true)
-            .write(true)
-            .read(false)
-            .open(file_path)
-        {
-            // if .gitattribute is new, just create it.
-            for x in FILES_CONTROLLED_BY_THIS_TOOL {
-                file.write(x.as_bytes())?;
-                file.write(b" ")?;
-                file.write(FILE_ATTRIBUTES.as_bytes())?;
-                file.write(b"\n")?;
-            }
-            file.flush()?;
-            drop(file);
-            return Ok(());
-        }
+        let path = resolve_attributes_path(root.as_deref(), self.attributes_path.as_deref());
+        update_attributes_at(&path, self.merge_union)
+    }
+
+    fn configure_info_attributes(&self) -> Result<()> {
+        let git_dir = crate::git::git_dir().context("not in a git repository")?;
+        update_attributes_at(&git_dir.join("info").join("attributes"), self.merge_union)
+    }
+}
+
+fn config_installed(options: &GitConfigOptions) -> Result<bool> {
+    config_installed_with(Command::new("git"), options)
+}
 
-        // the file should be exist. open as read&write
-        let mut file = OpenOptions::new().read(true).write(true).open(file_path)?;
+pub(crate) fn config_installed_with(git: Command, options: &GitConfigOptions) -> Result<bool> {
+    options
+        .exists_with(git, "filter.vrc.clean", true)
+        .context("git config to check exists")
+}
 
-        let mut attr_file = String::new();
-        file.read_to_string(&mut attr_file)?;
-        file.seek(SeekFrom::Start(0))?;
-        file.write(update_attributes_file(attr_file.lines()).as_bytes())?;
+/// resolves the path of .gitattributes to configure. `.gitattributes` by default.
+/// relative path is resolved from the repository root, or the current directory if `root` is None.
+pub(crate) fn resolve_attributes_path(root: Option<&Path>, path: Option<&Path>) -> PathBuf {
+    let path = path.unwrap_or(Path::new(".gitattributes"));
+    match root {
+        Some(root) => root.join(path),
+        None => path.to_owned(),
+    }
+}
+
+/// checks attributes of unity files in the directory of the attributes file at `attributes_path`.
+/// info/attributes applies to the repository root, same as the default .gitattributes.
+pub(crate) fn attributes_installed_at(
+    git: impl Fn() -> Command,
+    root: &Path,
+    attributes_path: Option<&Path>,
+    merge_union: bool,
+) -> Result<bool> {
+    let path = resolve_attributes_path(Some(root), attributes_path);
+    let dir = path.parent().unwrap_or(root);
+    if !dir.is_dir() {
+        return Ok(false);
+    }
+    let git = || {
+        let mut command = git();
+        command.current_dir(dir);
+        command
+    };
+    attributes_installed_with(git, merge_union)
+}
+
+/// attributes of unity files checked to detect installation
+const INSTALLED_ATTRIBUTES: &[&str] = &["filter", "merge"];
+
+/// checks attributes of unity files with single git check-attr for all attributes and files.
+fn attributes_installed_with(git: impl Fn() -> Command, merge_union: bool) -> Result<bool> {
+    let attrs =
+        crate::git::check_attr_with(git(), INSTALLED_ATTRIBUTES, FILES_CONTROLLED_BY_THIS_TOOL)
+            .context("git check-attr to check attributes")?;
+    Ok(is_vrc_configured(attrs, merge_union))
+}
+
+/// returns true if all files are filtered with vrc and merged with union if `merge_union`
+fn is_vrc_configured(
+    mut attrs: impl Iterator<Item = (String, String, String)>,
+    merge_union: bool,
+) -> bool {
+    attrs.all(|(_file, kind, value)| match kind.as_str() {
+        "filter" => value == "vrc",
+        "merge" => !merge_union || value == "union",
+        _ => true,
+    })
+}
+
+fn update_attributes_at(file_path: &Path, merge_union: bool) -> Result<()> {
+    if let Some(parent) = file_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    // try create new attributes file.
+    if let Ok(mut file) = OpenOptions::new()
+        .create_new(true)
+        .write(true)
+        .read(false)
+        .open(file_path)
+    {
+        // if .gitattribute is new, just create it.
+        for x in FILES_CONTROLLED_BY_THIS_TOOL {
+            file.write(x.as_bytes())?;
+            file.write(b" ")?;
+            file.write(FILE_ATTRIBUTES.as_bytes())?;
+            if merge_union {
+                file.write(b" merge=union")?;
+            }
+            file.write(b"\n")?;
+        }
         file.flush()?;
         drop(file);
+        return Ok(());
+    }
 
-        Ok(())
+    // the file should be exist. open as read&write
+    let mut file = OpenOptions::new().read(true).write(true).open(file_path)?;
+
+    let mut attr_file = String::new();
+    file.read_to_string(&mut attr_file)?;
+    let updated = update_attributes_file(attr_file.lines(), merge_union);
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(updated.as_bytes())?;
+    // updated file may be shorter than original one if crlf is used
+    file.set_len(updated.len() as u64)?;
+    file.flush()?;
+    drop(file);
+
+    Ok(())
+}
+
+/// removes `filter=vrc` of unity files from the attributes file.
+/// lines without other attributes are removed.
+pub(crate) fn remove_attributes_at(file_path: &Path) -> Result<()> {
+    let attr_file = match std::fs::read_to_string(file_path) {
+        Ok(attr_file) => attr_file,
+        // nothing to remove
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+    let updated = remove_attributes_file(attr_file.lines());
+    if updated != attr_file {
+        std::fs::write(file_path, updated)?;
+    }
+    Ok(())
+}
+
+fn remove_attributes_file<'a>(lines: impl Iterator<Item = &'a str>) -> String {
+    let mut result = String::new();
+
+    for line in lines {
+        if let Some(first_non_ws) = line.find(|c: char| !c.is_ascii_whitespace()) {
+            let trimmed = &line[first_non_ws..];
+            // not a comment line
+            if trimmed.as_bytes()[0] != b'#' {
+                let name_end = trimmed
+                    .find(|c: char| c.is_ascii_whitespace())
+                    .unwrap_or(trimmed.len());
+                let name = &trimmed[..name_end];
+                let attrs = trimmed[name_end..].split_ascii_whitespace();
+                if FILES_CONTROLLED_BY_THIS_TOOL.contains(&name)
+                    && attrs.clone().any(|attr| attr == "filter=vrc")
+                {
+                    let attrs = attrs
+                        .filter(|&attr| attr != "filter=vrc")
+                        .collect::<Vec<_>>();
+                    if !attrs.is_empty() {
+                        result.push_str(&line[..first_non_ws]);
+                        result.push_str(name);
+                        result.push(' ');
+                        result.push_str(&attrs.join(" "));
+                        result.push('\n');
+                    }
+                    continue;
+                }
+            }
+        }
+        result.push_str(line);
+        result.push('\n');
     }
+
+    result
 }
 
-fn update_attributes_file<'a>(lines: impl Iterator<Item = &'a str>) -> String {
+fn update_attributes_file<'a>(lines: impl Iterator<Item = &'a str>, merge_union: bool) -> String {
     let mut result = String::new();
     let mut added = HashSet::with_capacity(3);
 
@@ -235,7 +454,11 @@ fn update_attributes_file<'a>(lines: impl Iterator<Item = &'a str>) -> String {
                     added.insert(name);
                     result.push_str(&line[..first_non_ws]);
                     result.push_str(&trimmed[..name_end]);
-                    result.push_str(&add_attributes(&trimmed[name_end..], "*.asset" == name));
+                    result.push_str(&add_attributes(
+                        &trimmed[name_end..],
+                        "*.asset" == name,
+                        merge_union,
+                    ));
                     result.push('\n');
                     continue;
                 }
@@ -253,6 +476,9 @@ fn update_attributes_file<'a>(lines: impl Iterator<Item = &'a str>) -> String {
             if &"*.asset" == name {
                 result.push_str(" unity-sort");
             }
+            if merge_union {
+                result.push_str(" merge=union");
+            }
             result.push('\n');
         }
     }
@@ -260,14 +486,15 @@ fn update_attributes_file<'a>(lines: impl Iterator<Item = &'a str>) -> String {
     result
 }
 
-fn add_attributes(mut attrs: &str, set_unity_sort: bool) -> String {
+fn add_attributes(mut attrs: &str, set_unity_sort: bool, merge_union: bool) -> String {
+    let merge = if merge_union { " merge=union" } else { "" };
     // fast path: if no attributes are defined, append our attributes
     if attrs.is_empty() {
-        return format!(" {}", FILE_ATTRIBUTES);
+        return format!(" {}{}", FILE_ATTRIBUTES, merge);
     }
 
     if attrs.trim().is_empty() {
-        return format!("{}{}", attrs, FILE_ATTRIBUTES);
+        return format!("{}{}{}", attrs, FILE_ATTRIBUTES, merge);
     }
 
     // parse & check for existence
@@ -276,6 +503,7 @@ fn add_attributes(mut attrs: &str, set_unity_sort: bool) -> String {
     let mut text_found = false;
     let mut eol_found = false;
     let mut unity_sort_found = false;
+    let mut merge_found = false;
 
     loop {
         if let Some(non_ws) = attrs.find(|c: char| !c.is_ascii_whitespace()) {
@@ -309,6 +537,14 @@ fn add_attributes(mut attrs: &str, set_unity_sort: bool) -> String {
             eol_found = true
         } else if attr == "unity-sort" || attr.starts_with("unity-sort=") {
             unity_sort_found = true;
+        } else if attr == "merge" || attr.starts_with("merge=") {
+            if merge_union && attr != "merge=union" {
+                warn!(
+                    "configured attribute merge for unity files is kept: {}",
+                    attr
+                );
+            }
+            merge_found = true;
         }
     }
 
@@ -341,15 +577,400 @@ fn add_attributes(mut attrs: &str, set_unity_sort: bool) -> String {
         append_attr(&mut result, "unity-sort");
     }
 
+    if !merge_found && merge_union {
+        append_attr(&mut result, "merge=union");
+    }
+
     result
 }
 
 #[cfg(test)]
 mod test {
+    #[test]
+    fn attributes_path() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join(format!(
+            "git-vrc-test-attributes-path-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        let attributes = dir.join("SubProject").join(".gitattributes");
+
+        let app = <super::App as clap::Parser>::parse_from([
+            "install",
+            "--attributes-path",
+            attributes.to_str().unwrap(),
+        ]);
+        app.configure_attributes(true)?;
+        assert_eq!(
+            std::fs::read_to_string(&attributes)?,
+            format!(
+                concat!("*.asset {0}\n", "*.prefab {0}\n", "*.unity {0}\n",),
+                super::FILE_ATTRIBUTES
+            )
+        );
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_attributes_path() {
+        use super::resolve_attributes_path;
+        use std::path::Path;
+        let root = Path::new("/repo");
+        let sub_project = Path::new("SubProject/.gitattributes");
+        // .gitattributes at the repository root by default
+        assert_eq!(
+            resolve_attributes_path(Some(root), None),
+            root.join(".gitattributes")
+        );
+        assert_eq!(
+            resolve_attributes_path(Some(root), Some(sub_project)),
+            root.join(sub_project)
+        );
+        let absolute = Path::new("/other/.gitattributes");
+        assert_eq!(
+            resolve_attributes_path(Some(root), Some(absolute)),
+            absolute
+        );
+        // the current directory outside of a repository
+        assert_eq!(
+            resolve_attributes_path(None, None),
+            Path::new(".gitattributes")
+        );
+        assert_eq!(
+            resolve_attributes_path(None, Some(sub_project)),
+            sub_project
+        );
+    }
+
+    #[test]
+    fn check_installed() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join(format!(
+            "git-vrc-test-check-installed-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir)?;
+        let git = || {
+            let mut command = std::process::Command::new("git");
+            command
+                .current_dir(&dir)
+                .env("GIT_CONFIG_NOSYSTEM", "1")
+                .env("GIT_CONFIG_GLOBAL", dir.join("global-config"));
+            command
+        };
+        let installed = |args: &[&str]| {
+            let app =
+                <super::App as clap::Parser>::parse_from(["install", "--check"].iter().chain(args));
+            app.check_installed_with(git).is_ok()
+        };
+        assert!(git().args(["init", "-q"]).status()?.success());
+        let sub_project = dir.join("SubProject").join(".gitattributes");
+        let sub_project = sub_project.to_str().unwrap();
+
+        let nothing = installed(&[]);
+        let config = git()
+            .args([
+                "config",
+                "--global",
+                "filter.vrc.clean",
+                "git vrc clean --file %f",
+            ])
+            .status()?;
+        assert!(config.success());
+        let config_only = installed(&[]);
+        let sub_project_not_found = installed(&["--attributes-path", sub_project]);
+        super::update_attributes_at(std::path::Path::new(sub_project), false)?;
+        let sub_project_installed = installed(&["--attributes-path", sub_project]);
+        let sub_project_only = installed(&[]);
+        let info_not_installed = installed(&["--info-attributes"]);
+        super::update_attributes_at(&dir.join(".git").join("info").join("attributes"), false)?;
+        let info_installed = installed(&["--info-attributes"]);
+        std::fs::remove_dir_all(&dir)?;
+
+        assert!(!nothing);
+        assert!(!config_only);
+        assert!(!sub_project_not_found);
+        assert!(sub_project_installed);
+        // .gitattributes of SubProject doesn't affect files in the root
+        assert!(!sub_project_only);
+        assert!(!info_not_installed);
+        assert!(info_installed);
+        Ok(())
+    }
+
+    #[test]
+    fn git_config_unset() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join(format!(
+            "git-vrc-test-git-config-unset-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir)?;
+        let config = dir.join("global-config");
+        std::fs::write(&config, "")?;
+        let git = || {
+            let mut command = std::process::Command::new("git");
+            command
+                .current_dir(&dir)
+                .env("GIT_CONFIG_NOSYSTEM", "1")
+                .env("GIT_CONFIG_GLOBAL", &config);
+            command
+        };
+
+        let options = super::GitConfigOptions {
+            system: false,
+            global: true,
+            local: false,
+            worktree: false,
+        };
+        assert!(!options.exists_with(git(), "filter.vrc.clean", true)?);
+        options.set_with(git(), "filter.vrc.clean", "git vrc clean --file %f")?;
+        assert!(options.exists_with(git(), "filter.vrc.clean", false)?);
+        assert!(options.exists_with(git(), "filter.vrc.clean", true)?);
+        options.unset_with(git(), "filter.vrc.clean")?;
+        assert!(!options.exists_with(git(), "filter.vrc.clean", false)?);
+        assert!(!options.exists_with(git(), "filter.vrc.clean", true)?);
+        // unset of absent key succeeds to make uninstall idempotent
+        options.unset_with(git(), "filter.vrc.clean")?;
+        options.unset_with(git(), "filter.vrc.smudge")?;
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn is_vrc_configured() {
+        let attrs = |kind: &str, values: [&str; 3]| {
+            super::FILES_CONTROLLED_BY_THIS_TOOL
+                .iter()
+                .zip(values)
+                .map(|(file, value)| (file.to_string(), kind.to_owned(), value.to_owned()))
+                .collect::<Vec<_>>()
+                .into_iter()
+        };
+        assert!(super::is_vrc_configured(
+            attrs("filter", ["vrc", "vrc", "vrc"]),
+            false
+        ));
+        assert!(!super::is_vrc_configured(
+            attrs("filter", ["vrc", "unspecified", "vrc"]),
+            false
+        ));
+        assert!(!super::is_vrc_configured(
+            attrs("filter", ["lfs", "vrc", "vrc"]),
+            false
+        ));
+        let merge = ["union", "unspecified", "union"];
+        assert!(super::is_vrc_configured(attrs("merge", merge), false));
+        assert!(!super::is_vrc_configured(attrs("merge", merge), true));
+        assert!(super::is_vrc_configured(
+            attrs("merge", ["union", "union", "union"]),
+            true
+        ));
+    }
+
+    #[test]
+    fn attributes_installed() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join(format!(
+            "git-vrc-test-attributes-installed-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir)?;
+        let spawned = std::cell::Cell::new(0);
+        let git = || {
+            spawned.set(spawned.get() + 1);
+            let mut command = std::process::Command::new("git");
+            command
+                .current_dir(&dir)
+                .env("GIT_CONFIG_NOSYSTEM", "1")
+                .env("GIT_CONFIG_GLOBAL", dir.join("global-config"));
+            command
+        };
+        assert!(git().args(["init", "-q"]).status()?.success());
+        spawned.set(0);
+
+        let not_installed = super::attributes_installed_with(git, false)?;
+        std::fs::write(
+            dir.join(".gitattributes"),
+            format!(
+                concat!("*.asset {0}\n", "*.prefab {0}\n", "*.unity {0}\n",),
+                super::FILE_ATTRIBUTES
+            ),
+        )?;
+        let installed = super::attributes_installed_with(git, false)?;
+        let without_merge_union = super::attributes_installed_with(git, true)?;
+        std::fs::remove_dir_all(&dir)?;
+
+        assert!(!not_installed);
+        assert!(installed);
+        assert!(!without_merge_union);
+        // single check-attr for all attributes and files on each check
+        assert_eq!(spawned.get(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn update_attributes_at() -> anyhow::Result<()> {
+        let git_dir = std::env::temp_dir().join(format!(
+            "git-vrc-test-update-attributes-at-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&git_dir);
+        let info_attributes = git_dir.join("info").join("attributes");
+
+        super::update_attributes_at(&info_attributes, false)?;
+        assert_eq!(
+            std::fs::read_to_string(&info_attributes)?,
+            format!(
+                concat!("*.asset {0}\n", "*.prefab {0}\n", "*.unity {0}\n",),
+                super::FILE_ATTRIBUTES
+            )
+        );
+
+        std::fs::write(&info_attributes, "* text=auto\n")?;
+        super::update_attributes_at(&info_attributes, false)?;
+        assert_eq!(
+            std::fs::read_to_string(&info_attributes)?,
+            format!(
+                concat!(
+                    "* text=auto\n",
+                    "*.asset {0} unity-sort\n",
+                    "*.prefab {0}\n",
+                    "*.unity {0}\n",
+                ),
+                super::FILE_ATTRIBUTES
+            )
+        );
+
+        // lines are appended on the new line without blank line
+        // even if the file doesn't end with newline
+        for original in ["* text=auto", "* text=auto\n"] {
+            std::fs::write(&info_attributes, original)?;
+            super::update_attributes_at(&info_attributes, false)?;
+            assert_eq!(
+                std::fs::read_to_string(&info_attributes)?,
+                format!(
+                    concat!(
+                        "* text=auto\n",
+                        "*.asset {0} unity-sort\n",
+                        "*.prefab {0}\n",
+                        "*.unity {0}\n",
+                    ),
+                    super::FILE_ATTRIBUTES
+                ),
+                "{:?}",
+                original
+            );
+        }
+
+        std::fs::remove_dir_all(&git_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn update_attributes_at_existing() -> anyhow::Result<()> {
+        let file = std::env::temp_dir().join(format!(
+            "git-vrc-test-update-attributes-at-existing-{}",
+            std::process::id()
+        ));
+        let original = concat!(
+            "# Auto detect text files and perform LF normalization\n",
+            "* text=auto\n",
+            "\n",
+            "# Git LFS\n",
+            "*.png filter=lfs diff=lfs merge=lfs -text\n",
+            "*.fbx filter=lfs diff=lfs merge=lfs -text\n",
+            "\n",
+            "# Unity files\n",
+            "*.asset filter=vrc eol=lf text=auto unity-sort linguist-generated\n",
+            "  *.prefab filter=vrc eol=lf text=auto merge=unityyamlmerge\n",
+            "*.unity merge=unityyamlmerge\n",
+            "# *.unity -text\n",
+            "\n",
+            "*.cs diff=csharp\n",
+        );
+        let expected = concat!(
+            "# Auto detect text files and perform LF normalization\n",
+            "* text=auto\n",
+            "\n",
+            "# Git LFS\n",
+            "*.png filter=lfs diff=lfs merge=lfs -text\n",
+            "*.fbx filter=lfs diff=lfs merge=lfs -text\n",
+            "\n",
+            "# Unity files\n",
+            "*.asset filter=vrc eol=lf text=auto unity-sort linguist-generated\n",
+            "  *.prefab filter=vrc eol=lf text=auto merge=unityyamlmerge\n",
+            "*.unity merge=unityyamlmerge filter=vrc text eol=lf\n",
+            "# *.unity -text\n",
+            "\n",
+            "*.cs diff=csharp\n",
+        );
+
+        std::fs::write(&file, original)?;
+        super::update_attributes_at(&file, false)?;
+        assert_eq!(std::fs::read_to_string(&file)?, expected);
+
+        // second run should not change anything
+        super::update_attributes_at(&file, false)?;
+        assert_eq!(std::fs::read_to_string(&file)?, expected);
+
+        // crlf file will be shorter. no garbage should be left
+        std::fs::write(&file, original.replace('\n', "\r\n"))?;
+        super::update_attributes_at(&file, false)?;
+        assert_eq!(std::fs::read_to_string(&file)?, expected);
+
+        std::fs::remove_file(&file)?;
+        Ok(())
+    }
+
+    #[test]
+    fn remove_attributes_at() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join(format!(
+            "git-vrc-test-remove-attributes-at-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        let file = dir.join("SubProject").join(".gitattributes");
+
+        // absent file is not created
+        super::remove_attributes_at(&file)?;
+        assert!(!file.exists());
+
+        std::fs::create_dir_all(file.parent().unwrap())?;
+        std::fs::write(
+            &file,
+            concat!(
+                "* text=auto\n",
+                "# *.asset filter=vrc\n",
+                "*.asset filter=vrc eol=lf text=auto unity-sort\n",
+                "  *.prefab filter=vrc\n",
+                "*.unity merge=unityyamlmerge\n",
+                "*.png filter=vrc\n",
+            ),
+        )?;
+        super::remove_attributes_at(&file)?;
+        assert_eq!(
+            std::fs::read_to_string(&file)?,
+            concat!(
+                "* text=auto\n",
+                "# *.asset filter=vrc\n",
+                "*.asset eol=lf text=auto unity-sort\n",
+                "*.unity merge=unityyamlmerge\n",
+                "*.png filter=vrc\n",
+            ),
+        );
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
     #[test]
     fn update_attributes_file() {
         assert_eq!(
-            super::update_attributes_file(["* text=auto", "* eol=lf",].into_iter()),
+            super::update_attributes_file(["* text=auto", "* eol=lf",].into_iter(), false),
             format!(
                 concat!(
                     "* text=auto\n",
@@ -363,7 +984,7 @@ mod test {
         );
 
         assert_eq!(
-            super::update_attributes_file([].into_iter()),
+            super::update_attributes_file([].into_iter(), false),
             format!(
                 concat!(
                     "*.asset {0} unity-sort\n",
@@ -376,7 +997,8 @@ mod test {
 
         assert_eq!(
             super::update_attributes_file(
-                ["*.asset  eol=lf", "*.prefab text eol=lf   ",].into_iter()
+                ["*.asset  eol=lf", "*.prefab text eol=lf   ",].into_iter(),
+                false
             ),
             format!(
                 concat!(
@@ -395,7 +1017,8 @@ mod test {
                     format!("*.prefab {0}", super::FILE_ATTRIBUTES).as_str(),
                     format!("*.unity {0}", super::FILE_ATTRIBUTES).as_str(),
                 ]
-                .into_iter()
+                .into_iter(),
+                false
             ),
             format!(
                 concat!(
@@ -407,8 +1030,44 @@ mod test {
             )
         );
     }
+
+    #[test]
+    fn update_attributes_file_merge_union() {
+        assert_eq!(
+            super::update_attributes_file([].into_iter(), true),
+            format!(
+                concat!(
+                    "*.asset {0} unity-sort merge=union\n",
+                    "*.prefab {0} merge=union\n",
+                    "*.unity {0} merge=union\n",
+                ),
+                super::FILE_ATTRIBUTES
+            )
+        );
+
+        assert_eq!(
+            super::update_attributes_file(
+                [
+                    "*.asset eol=lf",
+                    "*.prefab",
+                    "*.unity filter=vrc eol=lf text merge=unityyamlmerge",
+                ]
+                .into_iter(),
+                true
+            ),
+            format!(
+                concat!(
+                    "*.asset eol=lf filter=vrc text unity-sort merge=union\n",
+                    "*.prefab {0} merge=union\n",
+                    "*.unity filter=vrc eol=lf text merge=unityyamlmerge\n",
+                ),
+                super::FILE_ATTRIBUTES
+            )
+        );
+    }
 }
 
 const FILE_ATTRIBUTES: &'static str = "filter=vrc eol=lf text=auto";
 
-const FILES_CONTROLLED_BY_THIS_TOOL: &'static [&'static str] = &["*.asset", "*.prefab", "*.unity"];
+pub(crate) const FILES_CONTROLLED_BY_THIS_TOOL: &'static [&'static str] =
+    &["*.asset", "*.prefab", "*.unity"];
@@ -0,0 +1,96 @@
+use crate::git::check_attr_with;
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use std::io::{stdout, Write};
+use std::process::{Command, Stdio};
+
+#[derive(Parser)]
+/// Lists files in the working tree which will be cleaned by git-vrc
+pub(crate) struct App {}
+
+impl App {
+    pub(crate) fn run(self) -> Result<()> {
+        let root = crate::git::repo_root().context("not in a git repository")?;
+        let files = list_files(|| {
+            let mut command = Command::new("git");
+            command.current_dir(&root);
+            command
+        })?;
+
+        let stdout = stdout();
+        let mut stdout = stdout.lock();
+        for file in files {
+            writeln!(stdout, "{}", file)?;
+        }
+        stdout.flush()?;
+        Ok(())
+    }
+}
+
+/// lists tracked and untracked but not ignored files with `filter=vrc`
+fn list_files(git: impl Fn() -> Command) -> Result<Vec<String>> {
+    let output = git()
+        .args([
+            "ls-files",
+            "-z",
+            "--cached",
+            "--others",
+            "--exclude-standard",
+        ])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        bail!("git ls-files command returns non-zero value");
+    }
+    let files = String::from_utf8(output.stdout).context("ls-files returns non-utf8")?;
+    let files = files.split_terminator('\0').collect::<Vec<_>>();
+
+    let mut result = Vec::new();
+    // check in chunks to not exceed the limit of command line length
+    for chunk in files.chunks(256) {
+        for (file, _, value) in check_attr_with(git(), &["filter"], chunk)? {
+            if value == "vrc" {
+                result.push(file);
+            }
+        }
+    }
+    Ok(result)
+}
+
+#[test]
+fn list_files_test() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("git-vrc-test-list-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("Assets"))?;
+    let git = || {
+        let mut command = Command::new("git");
+        command.current_dir(&dir);
+        command
+    };
+    assert!(git().args(["init", "-q"]).status()?.success());
+    std::fs::write(
+        dir.join(".gitattributes"),
+        "*.asset filter=vrc\n*.unity filter=vrc\nLocal.asset -filter\n",
+    )?;
+    std::fs::write(dir.join(".gitignore"), "Library/\n")?;
+    for file in [
+        "Assets/Scene.unity",
+        "Assets/Settings.asset",
+        "Assets/Local.asset",
+        "Assets/Script.cs",
+        "Library/Cache.asset",
+    ] {
+        let path = dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(path, "")?;
+    }
+
+    let files = list_files(git);
+    std::fs::remove_dir_all(&dir)?;
+
+    let mut files = files?;
+    files.sort();
+    assert_eq!(files, vec!["Assets/Scene.unity", "Assets/Settings.asset"]);
+    Ok(())
+}
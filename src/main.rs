@@ -2,9 +2,14 @@
 mod macros;
 
 mod clean;
+mod config;
+mod doctor;
+mod dump_tokens;
 mod git;
 mod install;
+mod list;
 mod smudge;
+mod uninstall;
 mod yaml;
 
 use anyhow::Result;
@@ -14,23 +19,62 @@ use simple_logger::SimpleLogger;
 
 #[derive(Parser)]
 #[clap(author, version, about)]
+struct Args {
+    /// suppress warnings. errors are still shown.
+    #[clap(short = 'q', long = "quiet", global = true)]
+    quiet: bool,
+    #[clap(subcommand)]
+    command: Commands,
+}
+
+#[derive(clap::Subcommand)]
 enum Commands {
     Install(install::App),
+    Uninstall(uninstall::App),
     Smudge(smudge::App),
     Clean(clean::App),
+    Doctor(doctor::App),
+    List(list::App),
+    #[clap(hide = true)]
+    DumpTokens(dump_tokens::App),
 }
 
 fn main() -> Result<()> {
+    let args: Args = Args::parse();
     SimpleLogger::new()
-        .with_level(LevelFilter::Info)
+        .with_level(log_level(args.quiet))
         .env()
         .init()
         .unwrap();
-    let args: Commands = Commands::parse();
 
-    match args {
+    match args.command {
         Commands::Install(app) => app.run(),
+        Commands::Uninstall(app) => app.run(),
         Commands::Smudge(app) => app.run(),
         Commands::Clean(app) => app.run(),
+        Commands::Doctor(app) => app.run(),
+        Commands::List(app) => app.run(),
+        Commands::DumpTokens(app) => app.run(),
+    }
+}
+
+fn log_level(quiet: bool) -> LevelFilter {
+    if quiet {
+        LevelFilter::Error
+    } else {
+        LevelFilter::Info
     }
 }
+
+#[test]
+fn quiet_test() {
+    let args = Args::parse_from(["git-vrc", "clean", "-q"]);
+    assert!(args.quiet);
+    // warnings like unknown unity-sort attribute are suppressed
+    assert!(log::Level::Warn > log_level(args.quiet));
+    assert!(log::Level::Error <= log_level(args.quiet));
+
+    let args = Args::parse_from(["git-vrc", "clean"]);
+    assert!(!args.quiet);
+    assert!(log::Level::Warn <= log_level(args.quiet));
+}
@@ -1,21 +1,72 @@
 use clap::Parser;
+use std::fs::File;
 use std::io;
+use std::io::{Read, Write};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 /// Smudge file. This is currently cat command but some feature can be added later.
 pub(crate) struct App {
     #[clap(long = "file")]
     file: Option<String>,
+    /// read from this file instead of stdin
+    #[clap(long = "input-file")]
+    input_file: Option<PathBuf>,
+    /// write to this file instead of stdout
+    #[clap(long = "output-file")]
+    output_file: Option<PathBuf>,
 }
 
 impl App {
     pub(crate) fn run(self) -> anyhow::Result<()> {
-        let mut stdin = io::stdin();
-        let mut stdout = io::stdout();
+        let mut input: Box<dyn Read> = match &self.input_file {
+            Some(path) => Box::new(File::open(path)?),
+            None => Box::new(io::stdin()),
+        };
+        let mut output: Box<dyn Write> = match &self.output_file {
+            Some(path) => Box::new(File::create(path)?),
+            None => Box::new(io::stdout()),
+        };
 
-        io::copy(&mut stdin, &mut stdout)?;
-        io::Write::flush(&mut stdout)?;
+        self.smudge(&mut input, &mut output)?;
+        output.flush()?;
 
         Ok(())
     }
+
+    fn smudge(&self, input: &mut impl Read, output: &mut impl Write) -> anyhow::Result<()> {
+        io::copy(input, output)?;
+        Ok(())
+    }
+}
+
+#[test]
+fn smudge_files() -> anyhow::Result<()> {
+    use std::ffi::OsStr;
+
+    let dir = std::env::temp_dir();
+    let input = dir.join(format!("git-vrc-test-smudge-input-{}", std::process::id()));
+    let output = dir.join(format!("git-vrc-test-smudge-output-{}", std::process::id()));
+    let yaml = concat!(
+        "%YAML 1.1\n",
+        "%TAG !u! tag:unity3d.com,2011:\n",
+        "--- !u!1 &1\n",
+        "GameObject:\n",
+        "  m_ObjectHideFlags: 0\n",
+    );
+    std::fs::write(&input, yaml)?;
+
+    App::parse_from([
+        OsStr::new("smudge"),
+        OsStr::new("--input-file"),
+        input.as_os_str(),
+        OsStr::new("--output-file"),
+        output.as_os_str(),
+    ])
+    .run()?;
+    assert_eq!(std::fs::read_to_string(&output)?, yaml);
+
+    std::fs::remove_file(&input)?;
+    std::fs::remove_file(&output)?;
+    Ok(())
 }
@@ -0,0 +1,98 @@
+use crate::install::{remove_attributes_at, resolve_attributes_path, GitConfigOptions};
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::path::PathBuf;
+use std::process::Command;
+
+#[derive(Parser)]
+/// Uninstalls git-vrc from git config
+pub(crate) struct App {
+    #[clap(flatten)]
+    git_config_options: GitConfigOptions,
+    /// remove filter=vrc from .gitattributes
+    #[clap(long)]
+    attributes: bool,
+    /// path to .gitattributes to remove filter=vrc from instead of .gitattributes at the repository
+    /// root. relative path is resolved from the repository root. implies --attributes
+    #[clap(long)]
+    attributes_path: Option<PathBuf>,
+}
+
+impl App {
+    pub(crate) fn run(mut self) -> Result<()> {
+        self.git_config_options.default_to_system();
+
+        // unset succeeds even if the key doesn't exist
+        for key in CONFIG_KEYS {
+            self.git_config_options
+                .unset(key)
+                .context("git config to unset")?;
+        }
+
+        if self.attributes || self.attributes_path.is_some() {
+            self.remove_attributes_with(Command::new("git"))?;
+        }
+
+        Ok(())
+    }
+
+    fn remove_attributes_with(&self, git: Command) -> Result<()> {
+        let root = crate::git::repo_root_with(git);
+        let path = resolve_attributes_path(root.as_deref(), self.attributes_path.as_deref());
+        remove_attributes_at(&path)
+    }
+}
+
+const CONFIG_KEYS: &[&str] = &[
+    "filter.vrc.smudge",
+    "filter.vrc.clean",
+    "filter.vrc.required",
+];
+
+#[test]
+fn remove_attributes_from_subdirectory() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!(
+        "git-vrc-test-uninstall-from-subdirectory-{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    let subdirectory = dir.join("Assets").join("Scenes");
+    std::fs::create_dir_all(&subdirectory)?;
+    let status = Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(&dir)
+        .status()?;
+    assert!(status.success());
+    std::fs::write(dir.join(".gitattributes"), "*.unity filter=vrc eol=lf\n")?;
+    std::fs::create_dir_all(dir.join("SubProject"))?;
+    std::fs::write(
+        dir.join("SubProject").join(".gitattributes"),
+        "*.prefab filter=vrc eol=lf\n",
+    )?;
+    let git = || {
+        let mut command = Command::new("git");
+        command.current_dir(&subdirectory);
+        command
+    };
+
+    // `git vrc uninstall --attributes` in Assets/Scenes
+    let app = <App as Parser>::parse_from(["uninstall", "--attributes"]);
+    app.remove_attributes_with(git())?;
+    let root = std::fs::read_to_string(dir.join(".gitattributes"))?;
+    let created = subdirectory.join(".gitattributes").exists();
+
+    let app = <App as Parser>::parse_from([
+        "uninstall",
+        "--attributes-path",
+        "SubProject/.gitattributes",
+    ]);
+    app.remove_attributes_with(git())?;
+    let sub_project = std::fs::read_to_string(dir.join("SubProject").join(".gitattributes"))?;
+    std::fs::remove_dir_all(&dir)?;
+
+    // relative paths are resolved from the repository root
+    assert_eq!(root, "*.unity eol=lf\n");
+    assert!(!created);
+    assert_eq!(sub_project, "*.prefab eol=lf\n");
+    Ok(())
+}
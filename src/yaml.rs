@@ -56,6 +56,39 @@ impl<'a> Iterator for YamlSeparated<'a> {
     }
 }
 
+/// line endings found in yaml. separators are found with `\n` so CRLF and mixed line endings
+/// are split in the same way and the original bytes are kept.
+#[derive(Default)]
+pub(crate) struct LineEndings {
+    lf: bool,
+    crlf: bool,
+}
+
+impl LineEndings {
+    pub(crate) fn add(&mut self, str: &str) {
+        for (i, _) in str.match_indices('\n') {
+            if str[..i].ends_with('\r') {
+                self.crlf = true;
+            } else {
+                self.lf = true;
+            }
+        }
+    }
+
+    pub(crate) fn is_mixed(&self) -> bool {
+        self.lf && self.crlf
+    }
+
+    /// the line ending to write new lines with. crlf only if no lines end with lf.
+    pub(crate) fn line_ending(&self) -> &'static str {
+        if self.crlf && !self.lf {
+            "\r\n"
+        } else {
+            "\n"
+        }
+    }
+}
+
 fn split_at_ceil_bytes(s: &str, mut cnt: usize) -> &str {
     if s.len() <= cnt {
         s
@@ -87,6 +120,96 @@ fn yaml_separated() {
     )
 }
 
+#[test]
+fn yaml_separated_mixed_line_endings() {
+    let yaml = concat!(
+        "HEADER\r\n",
+        "--- Separator\n",
+        "Content\r\n",
+        "--- Other Separator\r\n",
+        "Other Content\n",
+    );
+    assert_eq!(
+        YamlSeparated::new(yaml).collect::<Vec<_>>(),
+        vec![
+            ("", "HEADER\r\n"),
+            ("--- Separator\n", "Content\r\n"),
+            ("--- Other Separator\r\n", "Other Content\n"),
+        ]
+    );
+
+    let mut endings = LineEndings::default();
+    endings.add("a\nb\n");
+    assert!(!endings.is_mixed());
+    assert_eq!(endings.line_ending(), "\n");
+    let mut endings = LineEndings::default();
+    endings.add("a\r\nb\r\n");
+    assert!(!endings.is_mixed());
+    assert_eq!(endings.line_ending(), "\r\n");
+    endings.add("c\n");
+    assert!(endings.is_mixed());
+    let mut endings = LineEndings::default();
+    endings.add(yaml);
+    assert!(endings.is_mixed());
+    assert_eq!(endings.line_ending(), "\n");
+}
+
+#[test]
+fn yaml_separated_mutated() {
+    // splitting and parsing heading lines of mutated unity yaml never panics and
+    // splitting never loses any byte.
+    fn check(yaml: &str) {
+        let mut joined = String::new();
+        for (heading, body) in YamlSeparated::new(yaml) {
+            let _ = heading.parse::<ParsedHeadingLine>();
+            joined.push_str(heading);
+            joined.push_str(body);
+        }
+        assert_eq!(joined, yaml);
+    }
+
+    let seeds = [
+        concat!(
+            "%YAML 1.1\n",
+            "%TAG !u! tag:unity3d.com,2011:\n",
+            "--- !u!1 &1\n",
+            "GameObject:\n",
+            "  m_Name: Object\n",
+            "--- !u!114 &484105423 stripped\n",
+            "MonoBehaviour:\n",
+            "  m_PrefabInstance: {fileID: 2}\n",
+        ),
+        concat!(
+            "--- !u!1001 &-263184606691600302\n",
+            "PrefabInstance:\n",
+            "  m_Modification:\n",
+            "    m_Modifications: []\n",
+        ),
+    ];
+    let fragments = [
+        "\n",
+        "---",
+        "--- ",
+        "\n--- !u!1 &",
+        "&",
+        "-",
+        "!u!",
+        " stripped",
+        "\u{3042}",
+    ];
+
+    for seed in seeds {
+        check(seed);
+        for (pos, _) in seed.char_indices() {
+            check(&seed[..pos]);
+            check(&seed[pos..]);
+            for fragment in fragments {
+                check(&format!("{}{}{}", &seed[..pos], fragment, &seed[pos..]));
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct HeadingLineParsingErr(HeadingLineParsingErrInner);
 
@@ -111,6 +234,7 @@ impl Error for HeadingLineParsingErr {}
 
 #[derive(Eq, PartialEq, Debug)]
 pub(crate) struct ParsedHeadingLine {
+    class_id: Option<i64>,
     file_id: i64,
     is_stripped: bool,
 }
@@ -119,11 +243,19 @@ impl ParsedHeadingLine {
     #[allow(dead_code)]
     pub fn new(file_id: i64, is_stripped: bool) -> Self {
         Self {
+            class_id: None,
             file_id,
             is_stripped,
         }
     }
 
+    /// returns false if the class id in `!u!<class-id>` tag shows the object is not a GameObject.
+    /// returns true if the tag is not found or unknown format.
+    pub fn may_be_game_object(&self) -> bool {
+        // 1 is the class id of GameObject
+        matches!(self.class_id, None | Some(1))
+    }
+
     pub fn file_id(&self) -> i64 {
         self.file_id
     }
@@ -143,6 +275,15 @@ impl FromStr for ParsedHeadingLine {
             return Err(HeadingLineParsingErr(NoSeparator));
         }
         let s = s[4..].trim_start();
+        let class_id = if s.starts_with("!u!") {
+            let tag = &s["!u!".len()..];
+            let tag_end = tag
+                .find(|c: char| c.is_ascii_whitespace())
+                .unwrap_or(tag.len());
+            tag[..tag_end].parse().ok()
+        } else {
+            None
+        };
         let amp = s.find('&').ok_or(HeadingLineParsingErr(NoFileId))?;
         let s = &s[(amp + 1)..]; // +1: skil '&'
         let non_digit = s
@@ -165,6 +306,7 @@ impl FromStr for ParsedHeadingLine {
         }
 
         Ok(ParsedHeadingLine {
+            class_id,
             file_id,
             is_stripped,
         })
@@ -175,6 +317,7 @@ impl FromStr for ParsedHeadingLine {
 fn parsed_heading_line_parse() {
     assert_eq!(
         ParsedHeadingLine {
+            class_id: Some(29),
             file_id: 1,
             is_stripped: false,
         },
@@ -183,6 +326,7 @@ fn parsed_heading_line_parse() {
 
     assert_eq!(
         ParsedHeadingLine {
+            class_id: Some(114),
             file_id: -263184606691600302,
             is_stripped: false,
         },
@@ -191,9 +335,57 @@ fn parsed_heading_line_parse() {
 
     assert_eq!(
         ParsedHeadingLine {
+            class_id: Some(114),
             file_id: 484105423,
             is_stripped: true,
         },
         "--- !u!114 &484105423 stripped".parse().unwrap()
     );
+
+    assert_eq!(
+        ParsedHeadingLine {
+            class_id: Some(i64::MAX),
+            file_id: 1,
+            is_stripped: false,
+        },
+        "--- !u!9223372036854775807 &1".parse().unwrap()
+    );
+
+    assert_eq!(
+        ParsedHeadingLine {
+            class_id: Some(-1),
+            file_id: 1,
+            is_stripped: false,
+        },
+        "--- !u!-1 &1".parse().unwrap()
+    );
+
+    // overflow or unknown class id
+    assert_eq!(
+        ParsedHeadingLine {
+            class_id: None,
+            file_id: 1,
+            is_stripped: false,
+        },
+        "--- !u!9223372036854775808 &1".parse().unwrap()
+    );
+
+    assert_eq!(
+        ParsedHeadingLine {
+            class_id: None,
+            file_id: 1,
+            is_stripped: false,
+        },
+        "--- !u!Custom &1".parse().unwrap()
+    );
+}
+
+#[test]
+fn may_be_game_object() {
+    let parse = |heading: &str| heading.parse::<ParsedHeadingLine>().unwrap();
+    assert!(parse("--- !u!1 &1").may_be_game_object());
+    assert!(!parse("--- !u!4 &1").may_be_game_object());
+    // unknown class id may be a GameObject
+    assert!(parse("--- !u!Custom &1").may_be_game_object());
+    assert!(ParsedHeadingLine::new(1, false).may_be_game_object());
 }